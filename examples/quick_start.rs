@@ -1,3 +1,5 @@
+#![allow(clippy::needless_return)]
+
 // include the library
 use waow::*;
 
@@ -11,6 +13,7 @@ fn main() {
     height: 400,
     background_color: Color::from_rgba(0.0, 0.0, 0.0, 1.0),
     window_name: String::from("a colored square, waow!"),
+    ..Default::default()
   };
 
   // create a window with the given configuration
@@ -48,13 +51,7 @@ impl Run for App {
       // show a square if the user isn't pressing space
       if !input.key_held(KeyCode::Space) {
         // draw a square centered at the cursor position
-        canvas.draw_square(
-          (mouse_pos.0 - 10.0) as i16,
-          (mouse_pos.1 - 10.0) as i16,
-          20,
-          color,
-          None,
-        );
+        canvas.draw_square(mouse_pos.0 - 10.0, mouse_pos.1 - 10.0, 20.0, color, None);
       }
     }
   }
@@ -1,3 +1,5 @@
+#![allow(clippy::needless_return)]
+
 use waow::*;
 
 fn main() {
@@ -9,6 +11,7 @@ fn main() {
       height: 400,
       background_color: Color::from_rgba(0.0, 0.0, 0.0, 1.0),
       window_name: String::from("waow!"),
+      ..Default::default()
     },
   );
 }
@@ -30,67 +33,72 @@ impl App {
 impl Run for App {
   fn start(&mut self, _canvas: &mut Canvas) {
     // basic rectangle
-    self
-      .rectangles
-      .push(shapes::Rectangle::new(10, 10, 40, 20).with_fill(Color::from_rgba(1.0, 0.0, 0.0, 1.0)));
+    self.rectangles.push(
+      shapes::Rectangle::new(10.0, 10.0, 40.0, 20.0)
+        .with_fill(Color::from_rgba(1.0, 0.0, 0.0, 1.0)),
+    );
 
     // rectangle with border
     self.rectangles.push(
-      shapes::Rectangle::new(60, 10, 40, 50)
+      shapes::Rectangle::new(60.0, 10.0, 40.0, 50.0)
         .with_fill(Color::from_rgba(1.0, 0.0, 0.0, 1.0))
-        .with_border(Color::from_rgba(0.0, 0.0, 1.0, 1.0), 4),
+        .with_border(Color::from_rgba(0.0, 0.0, 1.0, 1.0), 4.0),
     );
 
     // border but no fill overlapping another rect
     self.rectangles.push(
-      shapes::Rectangle::new(120, 20, 40, 20).with_fill(Color::from_rgba(0.0, 1.0, 0.0, 1.0)),
+      shapes::Rectangle::new(120.0, 20.0, 40.0, 20.0)
+        .with_fill(Color::from_rgba(0.0, 1.0, 0.0, 1.0)),
     );
 
     self.rectangles.push(
-      shapes::Rectangle::new(130, 10, 20, 40).with_border(Color::from_rgba(0.0, 0.0, 1.0, 1.0), 5),
+      shapes::Rectangle::new(130.0, 10.0, 20.0, 40.0)
+        .with_border(Color::from_rgba(0.0, 0.0, 1.0, 1.0), 5.0),
     );
 
     // border with fill overlapping another rect
     self.rectangles.push(
-      shapes::Rectangle::new(170, 20, 40, 20).with_fill(Color::from_rgba(0.0, 1.0, 0.0, 1.0)),
+      shapes::Rectangle::new(170.0, 20.0, 40.0, 20.0)
+        .with_fill(Color::from_rgba(0.0, 1.0, 0.0, 1.0)),
     );
 
     self.rectangles.push(
-      shapes::Rectangle::new(180, 10, 20, 40)
-        .with_border(Color::from_rgba(0.0, 0.0, 1.0, 1.0), 4)
+      shapes::Rectangle::new(180.0, 10.0, 20.0, 40.0)
+        .with_border(Color::from_rgba(0.0, 0.0, 1.0, 1.0), 4.0)
         .with_fill(Color::from_rgba(0.0, 1.0, 1.0, 1.0)),
     );
 
     // basic circle
     self
       .circles
-      .push(shapes::Circle::new(30, 100, 20).with_fill(Color::from_rgba(1.0, 0.0, 0.0, 1.0)));
+      .push(shapes::Circle::new(30.0, 100.0, 20.0).with_fill(Color::from_rgba(1.0, 0.0, 0.0, 1.0)));
 
     // circle with border
     self.circles.push(
-      shapes::Circle::new(80, 100, 20)
+      shapes::Circle::new(80.0, 100.0, 20.0)
         .with_fill(Color::from_rgba(1.0, 0.0, 0.0, 1.0))
-        .with_border(Color::from_rgba(0.0, 0.0, 1.0, 1.0), 4),
+        .with_border(Color::from_rgba(0.0, 0.0, 1.0, 1.0), 4.0),
     );
 
     // border but no fill overlapping another circle
-    self
-      .circles
-      .push(shapes::Circle::new(150, 100, 20).with_fill(Color::from_rgba(0.0, 1.0, 0.0, 1.0)));
+    self.circles.push(
+      shapes::Circle::new(150.0, 100.0, 20.0).with_fill(Color::from_rgba(0.0, 1.0, 0.0, 1.0)),
+    );
 
-    self
-      .circles
-      .push(shapes::Circle::new(130, 100, 20).with_border(Color::from_rgba(0.0, 0.0, 1.0, 1.0), 5));
+    self.circles.push(
+      shapes::Circle::new(130.0, 100.0, 20.0)
+        .with_border(Color::from_rgba(0.0, 0.0, 1.0, 1.0), 5.0),
+    );
 
     // border with fill overlapping another circle
-    self
-      .circles
-      .push(shapes::Circle::new(220, 100, 20).with_fill(Color::from_rgba(0.0, 1.0, 0.0, 1.0)));
+    self.circles.push(
+      shapes::Circle::new(220.0, 100.0, 20.0).with_fill(Color::from_rgba(0.0, 1.0, 0.0, 1.0)),
+    );
 
     self.circles.push(
-      shapes::Circle::new(200, 100, 20)
+      shapes::Circle::new(200.0, 100.0, 20.0)
         .with_fill(Color::from_rgba(0.0, 1.0, 1.0, 1.0))
-        .with_border(Color::from_rgba(0.0, 0.0, 1.0, 1.0), 5),
+        .with_border(Color::from_rgba(0.0, 0.0, 1.0, 1.0), 5.0),
     );
   }
 
@@ -0,0 +1,69 @@
+//! Controls how colors are combined with what has already
+//! been drawn to the canvas
+//!
+//! The blend mode is set on the [`Canvas`](crate::Canvas) with
+//! `set_blend_mode()` and is used for every shape drawn after it
+
+/// The different ways a color can be combined with the pixels under it
+///
+/// All modes respect the alpha of the color being drawn, so a color
+/// with an alpha of `0.5` only applies half of the effect
+///
+/// # Examples
+/// ```
+/// use waow::*;
+///
+/// struct App {}
+/// impl Run for App {
+///   fn start(&mut self, _canvas: &mut Canvas) {}
+///   fn draw(&mut self, canvas: &mut Canvas, _input: &Input) {
+///     // overlapping additive circles glow where they meet
+///     canvas.set_blend_mode(BlendMode::Add);
+///     canvas.draw_shape(&shapes::Circle::new(40.0, 50.0, 30.0).with_fill(Color::from_rgba(0.6, 0.2, 0.0, 1.0)));
+///     canvas.draw_shape(&shapes::Circle::new(60.0, 50.0, 30.0).with_fill(Color::from_rgba(0.0, 0.2, 0.6, 1.0)));
+///     canvas.set_blend_mode(BlendMode::Normal);
+///   }
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BlendMode {
+  /// Draws the color on top, only showing what is below through transparency
+  #[default]
+  Normal,
+  /// Adds the color to what is below, brightening it
+  Add,
+  /// Multiplies the color with what is below, darkening it
+  Multiply,
+  /// The inverse of multiply, brightening what is below
+  Screen,
+  /// Multiplies dark areas and screens light areas of what is below
+  Overlay,
+  /// The absolute difference between the color and what is below
+  Difference,
+  /// Keeps the lightest of the color and what is below, per channel
+  Lighten,
+  /// Keeps the darkest of the color and what is below, per channel
+  Darken,
+}
+
+impl BlendMode {
+  /// Blends a single channel of the `src` color onto the `dst` color
+  pub(crate) fn blend_channel(&self, dst: f64, src: f64) -> f64 {
+    return match self {
+      BlendMode::Normal => src,
+      BlendMode::Add => (dst + src).min(1.0),
+      BlendMode::Multiply => dst * src,
+      BlendMode::Screen => dst + src - dst * src,
+      BlendMode::Overlay => {
+        if dst <= 0.5 {
+          2.0 * dst * src
+        } else {
+          1.0 - 2.0 * (1.0 - dst) * (1.0 - src)
+        }
+      }
+      BlendMode::Difference => (dst - src).abs(),
+      BlendMode::Lighten => dst.max(src),
+      BlendMode::Darken => dst.min(src),
+    };
+  }
+}
@@ -0,0 +1,140 @@
+//! Represents a view onto a world that is larger than the canvas
+//!
+//! The canvas keeps a camera that maps shapes drawn in world
+//! coordinates to pixels on the screen, see
+//! [`Canvas::set_camera()`](crate::Canvas::set_camera)
+
+use crate::{Input, Transform};
+
+/// How much one step of the mouse wheel zooms the camera in or out
+const WHEEL_ZOOM_FACTOR: f32 = 1.1;
+
+/// Shows the world from position (`x`, `y`) in the top left corner
+/// of the canvas, with every world unit `zoom` pixels wide
+///
+/// # Examples
+/// ```
+/// use waow::*;
+///
+/// let mut camera = Camera::new(100.0, 50.0, 2.0);
+/// assert_eq!(camera.world_to_screen(110.0, 60.0), (20.0, 20.0));
+/// assert_eq!(camera.screen_to_world(20.0, 20.0), (110.0, 60.0));
+///
+/// // zooming keeps the world position under (20, 20) in place
+/// camera.zoom_at(20.0, 20.0, 2.0);
+/// assert_eq!(camera.world_to_screen(110.0, 60.0), (20.0, 20.0));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Camera {
+  x: f32,
+  y: f32,
+  zoom: f32,
+}
+
+impl Camera {
+  /// Creates a camera at world position (`x`, `y`) with the given zoom
+  ///
+  /// # Panics
+  /// panics if `zoom` is not greater than `0.0`
+  pub fn new(x: f32, y: f32, zoom: f32) -> Self {
+    assert!(zoom > 0.0, "zoom must be greater than 0.0");
+    return Self { x, y, zoom };
+  }
+
+  /// Gets the world position shown in the top left corner of the canvas
+  pub fn get_position(&self) -> (f32, f32) {
+    return (self.x, self.y);
+  }
+
+  /// Sets the world position shown in the top left corner of the canvas
+  pub fn set_position(&mut self, x: f32, y: f32) {
+    self.x = x;
+    self.y = y;
+  }
+
+  /// Gets how many pixels wide each world unit is
+  pub fn get_zoom(&self) -> f32 {
+    return self.zoom;
+  }
+
+  /// Sets how many pixels wide each world unit is, keeping the
+  /// top left corner of the canvas in place
+  ///
+  /// # Panics
+  /// panics if `zoom` is not greater than `0.0`
+  pub fn set_zoom(&mut self, zoom: f32) {
+    assert!(zoom > 0.0, "zoom must be greater than 0.0");
+    self.zoom = zoom;
+  }
+
+  /// Gets the transform that maps world coordinates to screen coordinates
+  pub fn get_transform(&self) -> Transform {
+    return Transform::scaling(self.zoom, self.zoom)
+      .multiply(&Transform::translation(-self.x, -self.y));
+  }
+
+  /// Converts a world position to a position on the screen in pixels
+  pub fn world_to_screen(&self, x: f32, y: f32) -> (f32, f32) {
+    return ((x - self.x) * self.zoom, (y - self.y) * self.zoom);
+  }
+
+  /// Converts a position on the screen in pixels, such as
+  /// [`Input::mouse()`], to a world position
+  pub fn screen_to_world(&self, x: f32, y: f32) -> (f32, f32) {
+    return (x / self.zoom + self.x, y / self.zoom + self.y);
+  }
+
+  /// Moves the world across the screen by `x` and `y` pixels
+  pub fn pan(&mut self, x: f32, y: f32) {
+    self.x -= x / self.zoom;
+    self.y -= y / self.zoom;
+  }
+
+  /// Multiplies the zoom by `factor`, keeping the world position
+  /// under the screen position (`x`, `y`) in place
+  ///
+  /// # Panics
+  /// panics if `factor` is not greater than `0.0`
+  pub fn zoom_at(&mut self, x: f32, y: f32, factor: f32) {
+    assert!(factor > 0.0, "factor must be greater than 0.0");
+    let (world_x, world_y) = self.screen_to_world(x, y);
+    self.zoom *= factor;
+    self.x = world_x - x / self.zoom;
+    self.y = world_y - y / self.zoom;
+  }
+
+  /// Zooms in or out around the mouse when the mouse wheel is scrolled
+  ///
+  /// Returns whether the camera changed
+  pub fn zoom_with_wheel(&mut self, input: &Input) -> bool {
+    let scroll = input.scroll_diff();
+    return match input.mouse() {
+      Some((x, y)) if scroll != 0.0 => {
+        self.zoom_at(x, y, WHEEL_ZOOM_FACTOR.powf(scroll));
+        true
+      }
+      _ => false,
+    };
+  }
+
+  /// Moves the world along with the mouse while `button` is held
+  ///
+  /// The buttons are numbered the same as [`Input::mouse_held()`],
+  /// with `0` for left, `1` for right and `2` for middle.
+  /// Returns whether the camera changed
+  pub fn pan_with_drag(&mut self, input: &Input, button: usize) -> bool {
+    let (x, y) = input.mouse_diff();
+    if !input.mouse_held(button) || (x == 0.0 && y == 0.0) {
+      return false;
+    }
+
+    self.pan(x, y);
+    return true;
+  }
+}
+
+impl Default for Camera {
+  fn default() -> Self {
+    return Self::new(0.0, 0.0, 1.0);
+  }
+}
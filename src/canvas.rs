@@ -3,59 +3,1525 @@
 //!
 //! The canvas contains methods for drawing certain shapes,
 //! as well as drawing any [`Drawable`]
+//!
+//! Shapes are drawn onto numbered layers, which are drawn from the
+//! lowest number to the highest
+//!
+//! Drawing is split into bands of rows which are rasterized
+//! in parallel across all available CPU cores. Only the tiles of
+//! pixels that shapes were drawn to in this frame or the last are
+//! redrawn, the rest are kept from the previous frame
+
+use std::{
+  collections::BTreeMap,
+  fs::File,
+  io::{self, BufWriter},
+  mem,
+  path::Path,
+  sync::{Arc, Mutex},
+  thread::{self, available_parallelism},
+  time::Duration,
+};
+
+use crate::{
+  log_error,
+  recording::Recording,
+  shapes::{clamp_coordinate, Bounds, Drawable, Image, Rectangle},
+  timing::FrameClock,
+  transform::TransformedShape,
+  BlendMode, Camera, CanvasConfiguration, Color, FullscreenMode, Input, Transform,
+};
+
+/// The size of the square tiles that changes are tracked in,
+/// each band of pixels handed to a thread is one tile high
+const TILE_SIZE: usize = 16;
+
+/// How smoothly the edges of shapes are drawn
+///
+/// # Examples
+/// ```
+/// use waow::*;
+///
+/// struct App {}
+/// impl Run for App {
+///   fn start(&mut self, _canvas: &mut Canvas) {}
+///   fn draw(&mut self, canvas: &mut Canvas, _input: &Input) {
+///     canvas.draw_shape(&shapes::Circle::new(200.0, 200.0, 149.5).with_fill(Color::from_rgba(1.0, 1.0, 1.0, 1.0)));
+///   }
+/// }
+///
+/// let config = CanvasConfiguration {
+///   quality: RenderQuality::Antialiased,
+///   background_color: Color::from_rgba(0.0, 0.0, 0.0, 0.0),
+///   ..Default::default()
+/// };
+/// let width = config.width as usize;
+///
+/// let mut headless = Headless::new(App {}, config);
+/// headless.step();
+///
+/// // the pixel on the left edge of the circle is partly covered
+/// let alpha = headless.get_frame()[(200 * width + 50) * 4 + 3];
+/// assert!(alpha > 0 && alpha < 255);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RenderQuality {
+  /// Every pixel is either inside or outside of a shape, leaving jagged edges
+  #[default]
+  Fast,
+  /// Pixels on the edges of shapes are blended by how much of the pixel the
+  /// shape covers, see [`Drawable::get_color_antialiased()`]
+  Antialiased,
+  /// Every pixel is sampled `n` x `n` times at evenly spaced points within
+  /// it, and the samples are averaged, see [`Drawable::sample()`]
+  ///
+  /// Smooths the edges of any shape, but is `n * n` times slower
+  Supersampled(u8),
+}
+
+/// A shape waiting to be drawn, along with the state it was drawn with
+struct DrawCommand {
+  shape: Box<dyn Drawable>,
+  blend_mode: BlendMode,
+  clip: Option<Arc<Clip>>,
+}
+
+impl DrawCommand {
+  /// Gets the part of `region` that the object can draw to,
+  /// limited by both its own bounds and its clip
+  fn get_bounds(&self, region: &Bounds) -> Option<Bounds> {
+    let mut bounds = Some(*region);
+    if let Some(shape_bounds) = self.shape.get_bounds() {
+      bounds = bounds.and_then(|bounds| bounds.intersect(&shape_bounds));
+    }
+    if let Some(clip_bounds) = self.clip.as_ref().and_then(|clip| clip.bounds) {
+      bounds = bounds.and_then(|bounds| bounds.intersect(&clip_bounds));
+    }
+
+    return bounds;
+  }
+}
+
+/// The area that shapes are limited to, which is inside of
+/// all of the rectangles and shapes that were pushed
+#[derive(Clone)]
+struct Clip {
+  bounds: Option<Bounds>,
+  shapes: Vec<Arc<dyn Drawable>>,
+}
+
+impl Clip {
+  /// Limits the clip to the area also inside of `bounds`
+  fn intersect(&mut self, bounds: &Bounds) {
+    let bounds = match self.bounds {
+      Some(clip_bounds) => clip_bounds.intersect(bounds),
+      None => Some(*bounds),
+    };
+    self.bounds = Some(bounds.unwrap_or(Bounds::new(0, 0, 0, 0)));
+  }
+}
+
+/// A group of shapes that are drawn together
+struct Layer {
+  visible: bool,
+  opacity: f64,
+  objects: Vec<DrawCommand>,
+}
+
+impl Layer {
+  fn new() -> Self {
+    return Self {
+      visible: true,
+      opacity: 1.0,
+      objects: Vec::<DrawCommand>::new(),
+    };
+  }
+}
+
+pub struct Canvas {
+  width: u32,
+  height: u32,
+  background_color: Color,
+  persistent: bool,
+  quality: RenderQuality,
+  blend_mode: BlendMode,
+  layer: i32,
+  layers: BTreeMap<i32, Layer>,
+  clips: Vec<Arc<Clip>>,
+  transform: Transform,
+  transforms: Vec<Transform>,
+  camera: Camera,
+  camera_enabled: bool,
+  fullscreen: Option<FullscreenMode>,
+  interpolation: f32,
+  clock: FrameClock,
+  target_frame_rate: Option<u32>,
+  vsync: bool,
+  looping: bool,
+  redraw_requested: bool,
+  pixels: Vec<Color>,
+  dirty_tiles: Vec<bool>,
+  redraw_all: bool,
+  threads: usize,
+  recording: Option<Recording>,
+}
+
+impl Canvas {
+  /// Creates a canvas with the given configuration
+  ///
+  /// # Panics
+  /// panics if [`CanvasConfiguration::target_frame_rate`] is `Some(0)`
+  pub fn new(config: &CanvasConfiguration) -> Self {
+    assert!(
+      config.target_frame_rate != Some(0),
+      "frame rate must be greater than 0"
+    );
+    return Self {
+      width: config.width,
+      height: config.height,
+      background_color: config.background_color,
+      persistent: config.persistent,
+      quality: config.quality,
+      blend_mode: BlendMode::Normal,
+      layer: 0,
+      layers: BTreeMap::<i32, Layer>::new(),
+      clips: Vec::<Arc<Clip>>::new(),
+      transform: Transform::identity(),
+      transforms: Vec::<Transform>::new(),
+      camera: Camera::default(),
+      camera_enabled: true,
+      fullscreen: config.fullscreen,
+      interpolation: 0.0,
+      clock: FrameClock::default(),
+      target_frame_rate: config.target_frame_rate,
+      vsync: config.vsync,
+      looping: config.looping,
+      redraw_requested: false,
+      pixels: Vec::<Color>::new(),
+      dirty_tiles: Vec::<bool>::new(),
+      redraw_all: true,
+      threads: available_parallelism().map_or(1, |threads| threads.get()),
+      recording: None,
+    };
+  }
+
+  /// Creates a canvas that is not shown on the screen
+  ///
+  /// The canvas has a size of `width` x `height` and a transparent
+  /// background. Shapes can be drawn to it like any other canvas,
+  /// and then turned into an [`Image`] with [`Canvas::to_image()`]
+  ///
+  /// # Examples
+  /// ```
+  /// use waow::*;
+  ///
+  /// struct App {
+  ///   background: shapes::Image,
+  /// }
+  ///
+  /// impl App {
+  ///   pub fn create() -> Self {
+  ///     // draw the background once instead of every frame
+  ///     let mut offscreen = Canvas::new_offscreen(200, 200);
+  ///     for i in 0..10 {
+  ///       offscreen.draw_shape(
+  ///         &shapes::Circle::new(i as f32 * 20.0, 100.0, 15.0).with_fill(Color::from_rgba(0.2, 0.2, 0.2, 1.0)),
+  ///       );
+  ///     }
+  ///
+  ///     return Self {
+  ///       background: offscreen.to_image(0.0, 0.0),
+  ///     };
+  ///   }
+  /// }
+  ///
+  /// impl Run for App {
+  ///   fn start(&mut self, _canvas: &mut Canvas) {}
+  ///   fn draw(&mut self, canvas: &mut Canvas, _input: &Input) {
+  ///     canvas.draw_image(&self.background);
+  ///   }
+  /// }
+  /// ```
+  pub fn new_offscreen(width: u32, height: u32) -> Self {
+    return Self::new(&CanvasConfiguration {
+      width,
+      height,
+      background_color: Color::from_rgba(0.0, 0.0, 0.0, 0.0),
+      ..Default::default()
+    });
+  }
+
+  /// Gets the (width, height) of the canvas in pixels
+  ///
+  /// The size can change while the app is running,
+  /// see [`ScalingMode::Resize`](crate::ScalingMode::Resize)
+  pub fn get_size(&self) -> (u32, u32) {
+    return (self.width, self.height);
+  }
+
+  /// Changes the size of the canvas, clearing it to the background color
+  ///
+  /// Stops recording, since every frame of a GIF has to be the same size
+  pub(crate) fn resize(&mut self, width: u32, height: u32) {
+    if (width, height) == (self.width, self.height) {
+      return;
+    }
+
+    self.width = width;
+    self.height = height;
+    self.pixels = Vec::<Color>::new();
+    self.dirty_tiles = Vec::<bool>::new();
+    self.redraw_all = true;
+    if let Err(err) = self.stop_recording() {
+      log_error("canvas.stop_recording", err);
+    }
+  }
+
+  /// Sets whether the window covers the whole screen, and how
+  ///
+  /// `None` shows the canvas in a normal window. The window changes after
+  /// the current frame, and the canvas is fit into the screen using
+  /// [`CanvasConfiguration::scaling`]. Does nothing without a window
+  ///
+  /// # Examples
+  /// ```
+  /// use waow::*;
+  ///
+  /// struct App {}
+  /// impl Run for App {
+  ///   fn start(&mut self, _canvas: &mut Canvas) {}
+  ///   fn draw(&mut self, canvas: &mut Canvas, input: &Input) {
+  ///     if input.key_pressed(KeyCode::F) {
+  ///       canvas.set_fullscreen(Some(FullscreenMode::Borderless));
+  ///     }
+  ///     if input.key_pressed(KeyCode::Escape) {
+  ///       canvas.set_fullscreen(None);
+  ///     }
+  ///   }
+  /// }
+  /// ```
+  pub fn set_fullscreen(&mut self, fullscreen: Option<FullscreenMode>) {
+    self.fullscreen = fullscreen;
+  }
+
+  /// Gets how the window covers the screen, or `None` if it is
+  /// a normal window
+  pub fn get_fullscreen(&self) -> Option<FullscreenMode> {
+    return self.fullscreen;
+  }
+
+  /// Gets how far the current frame is between the last call to
+  /// [`Run::update()`](crate::Run::update) and the next, from `0.0` to `1.0`
+  ///
+  /// Drawing objects partway between where they were before and after
+  /// the last update keeps them moving smoothly, even when frames are
+  /// drawn more often than the app is updated
+  pub fn get_interpolation(&self) -> f32 {
+    return self.interpolation;
+  }
+
+  pub(crate) fn set_interpolation(&mut self, interpolation: f32) {
+    self.interpolation = interpolation;
+  }
+
+  /// Gets the number of the frame being drawn, starting at `1`
+  /// for the first frame
+  ///
+  /// # Examples
+  /// ```
+  /// use waow::*;
+  ///
+  /// struct App {}
+  /// impl Run for App {
+  ///   fn start(&mut self, _canvas: &mut Canvas) {}
+  ///   fn draw(&mut self, canvas: &mut Canvas, _input: &Input) {
+  ///     // move across the canvas at 60 pixels per second, whatever the frame rate
+  ///     let x = canvas.get_elapsed_time() * 60.0;
+  ///     canvas.draw_square(x % 100.0, 40.0, 20.0, Color::from_rgba(1.0, 1.0, 1.0, 1.0), None);
+  ///
+  ///     if canvas.get_frame_count() % 60 == 0 {
+  ///       println!("{:.1} fps", canvas.get_fps());
+  ///     }
+  ///   }
+  /// }
+  ///
+  /// let mut headless = Headless::new(App {}, CanvasConfiguration::default()).with_frame_rate(30);
+  /// headless.run(15);
+  ///
+  /// // headless apps use the virtual clock
+  /// let canvas = headless.get_canvas();
+  /// assert_eq!(canvas.get_frame_count(), 15);
+  /// assert_eq!(canvas.get_elapsed_time(), 0.5);
+  /// ```
+  pub fn get_frame_count(&self) -> u64 {
+    return self.clock.get_frame_count();
+  }
+
+  /// Gets the time in seconds from when the app started
+  /// to the current frame
+  ///
+  /// Headless apps use a virtual clock, so the time depends only
+  /// on the number of frames, see [`Headless`](crate::Headless)
+  pub fn get_elapsed_time(&self) -> f32 {
+    return self.clock.get_elapsed().as_secs_f32();
+  }
+
+  /// Gets the time in seconds between the last frame and the current one
+  pub fn get_delta_time(&self) -> f32 {
+    return self.clock.get_delta().as_secs_f32();
+  }
+
+  /// Gets the number of frames drawn per second, smoothed over
+  /// the last few frames so it doesn't jump around
+  pub fn get_fps(&self) -> f32 {
+    return self.clock.get_fps();
+  }
+
+  /// Starts a new frame, `delta` after the last one
+  pub(crate) fn tick(&mut self, delta: Duration) {
+    self.clock.tick(delta);
+  }
+
+  /// Sets the most frames that are drawn per second, or `None` to draw
+  /// frames as fast as possible
+  ///
+  /// Does nothing without a window, since headless apps are drawn
+  /// on a virtual clock, see [`Headless`](crate::Headless)
+  ///
+  /// # Panics
+  /// panics if `frame_rate` is `Some(0)`
+  pub fn set_target_frame_rate(&mut self, frame_rate: Option<u32>) {
+    assert!(frame_rate != Some(0), "frame rate must be greater than 0");
+    self.target_frame_rate = frame_rate;
+  }
+
+  /// Gets the most frames that are drawn per second,
+  /// or `None` if there is no limit
+  pub fn get_target_frame_rate(&self) -> Option<u32> {
+    return self.target_frame_rate;
+  }
+
+  /// Sets whether each frame waits for the screen to refresh before
+  /// being shown, which stops tearing but limits the frame rate to
+  /// the refresh rate of the screen
+  ///
+  /// The window's surface is rebuilt after the current frame.
+  /// Does nothing without a window
+  pub fn set_vsync(&mut self, vsync: bool) {
+    self.vsync = vsync;
+  }
+
+  /// Checks whether each frame waits for the screen to refresh
+  pub fn get_vsync(&self) -> bool {
+    return self.vsync;
+  }
+
+  /// Sets whether frames are drawn continuously
+  ///
+  /// When the canvas isn't looping, frames are only drawn when input
+  /// arrives, or when asked for with [`Canvas::redraw()`], so apps that
+  /// don't change on their own don't keep the CPU busy. Headless apps
+  /// are always drawn every step
+  ///
+  /// # Examples
+  /// ```
+  /// use waow::*;
+  ///
+  /// struct App {
+  ///   seed: u32,
+  /// }
+  ///
+  /// impl Run for App {
+  ///   fn start(&mut self, canvas: &mut Canvas) {
+  ///     canvas.set_looping(false);
+  ///   }
+  ///
+  ///   fn draw(&mut self, canvas: &mut Canvas, input: &Input) {
+  ///     // pressing a key draws a frame, which shows a new piece
+  ///     if input.key_pressed(KeyCode::Space) {
+  ///       self.seed += 1;
+  ///     }
+  ///     let x = (self.seed * 37 % 100) as f32;
+  ///     canvas.draw_square(x, 50.0, 10.0, Color::from_rgba(1.0, 1.0, 1.0, 1.0), None);
+  ///   }
+  /// }
+  /// ```
+  pub fn set_looping(&mut self, looping: bool) {
+    self.looping = looping;
+  }
+
+  /// Checks whether frames are drawn continuously
+  pub fn is_looping(&self) -> bool {
+    return self.looping;
+  }
+
+  /// Asks for another frame to be drawn, even if the canvas isn't looping,
+  /// see [`Canvas::set_looping()`]
+  ///
+  /// # Examples
+  /// ```
+  /// use waow::*;
+  ///
+  /// struct App {
+  ///   frames_left: u32,
+  /// }
+  ///
+  /// impl Run for App {
+  ///   fn start(&mut self, canvas: &mut Canvas) {
+  ///     canvas.set_looping(false);
+  ///   }
+  ///
+  ///   fn draw(&mut self, canvas: &mut Canvas, input: &Input) {
+  ///     // play a short animation after each click
+  ///     if input.mouse_pressed(0) {
+  ///       self.frames_left = 30;
+  ///     }
+  ///     if self.frames_left > 0 {
+  ///       self.frames_left -= 1;
+  ///       canvas.redraw();
+  ///     }
+  ///
+  ///     let size = self.frames_left as f32;
+  ///     canvas.draw_square(50.0, 50.0, size, Color::from_rgba(1.0, 1.0, 1.0, 1.0), None);
+  ///   }
+  /// }
+  /// ```
+  pub fn redraw(&mut self) {
+    self.redraw_requested = true;
+  }
+
+  pub(crate) fn is_redraw_requested(&self) -> bool {
+    return self.redraw_requested;
+  }
+
+  /// Clears the request made with [`Canvas::redraw()`]
+  pub(crate) fn clear_redraw_request(&mut self) {
+    self.redraw_requested = false;
+  }
+
+  /// Draws every shape drawn this frame into `frame` as RGBA bytes
+  ///
+  /// Each shape only visits the pixels within its bounds,
+  /// see [`Drawable::get_bounds()`]. The frame is split into bands
+  /// of rows that are drawn on separate threads
+  ///
+  /// Unless the canvas is persistent, the frame is cleared to the
+  /// background color first. Only tiles covered by shapes drawn in
+  /// this frame or the last are redrawn, since every other tile is
+  /// still the background color. If the canvas is recording, the frame
+  /// is also added to the recording
+  ///
+  /// # Examples
+  /// ```
+  /// use waow::*;
+  ///
+  /// struct App {
+  ///   x: f32,
+  /// }
+  ///
+  /// impl Run for App {
+  ///   fn start(&mut self, _canvas: &mut Canvas) {}
+  ///   fn draw(&mut self, canvas: &mut Canvas, _input: &Input) {
+  ///     canvas.draw_square(self.x, 0.0, 10.0, Color::from_rgba(1.0, 1.0, 1.0, 1.0), None);
+  ///     self.x += 100.0;
+  ///   }
+  /// }
+  ///
+  /// let mut headless = Headless::new(App { x: 0.0 }, CanvasConfiguration::default());
+  /// headless.step();
+  /// assert_eq!(headless.get_frame()[..4], [255, 255, 255, 255]);
+  ///
+  /// // the square moved to another tile, so its old tile is cleared
+  /// headless.step();
+  /// assert_eq!(headless.get_frame()[..4], [0, 0, 0, 255]);
+  /// assert_eq!(headless.get_frame()[100 * 4..][..4], [255, 255, 255, 255]);
+  /// ```
+  pub fn draw_to_buffer(&mut self, frame: &mut [u8]) {
+    let (width, height) = (self.width as usize, self.height as usize);
+    let tiles_wide = width.div_ceil(TILE_SIZE);
+    let tiles_high = height.div_ceil(TILE_SIZE);
+    if width == 0 || height == 0 {
+      self.end_frame();
+      return;
+    }
+
+    if self.pixels.len() != width * height || self.dirty_tiles.len() != tiles_wide * tiles_high {
+      self.pixels.clear();
+      self.pixels.resize(width * height, self.background_color);
+      self.redraw_all = true;
+    }
+
+    let canvas_bounds = Bounds::new(0, 0, self.width as i32, self.height as i32);
+    let mut drawn_tiles = vec![false; tiles_wide * tiles_high];
+    for layer in self.layers.values().filter(|layer| layer.visible) {
+      for object in layer.objects.iter() {
+        if let Some(bounds) = object.get_bounds(&canvas_bounds) {
+          for tile_y in bounds.y as usize / TILE_SIZE..=(bounds.bottom() - 1) as usize / TILE_SIZE {
+            for tile_x in bounds.x as usize / TILE_SIZE..=(bounds.right() - 1) as usize / TILE_SIZE {
+              drawn_tiles[tile_y * tiles_wide + tile_x] = true;
+            }
+          }
+        }
+      }
+    }
+
+    // tiles drawn to last frame need to be cleared back to the background
+    let mut dirty_tiles = drawn_tiles.clone();
+    if self.redraw_all {
+      dirty_tiles.fill(true);
+    } else if !self.persistent {
+      for (dirty, previous) in dirty_tiles.iter_mut().zip(self.dirty_tiles.iter()) {
+        *dirty |= *previous;
+      }
+    }
+    self.dirty_tiles = drawn_tiles;
+    self.redraw_all = false;
+
+    let mut pixels = mem::take(&mut self.pixels);
+    self.rasterize(&mut pixels, frame, &dirty_tiles, !self.persistent);
+    self.pixels = pixels;
+
+    self.end_frame();
+
+    if let Some(recording) = &mut self.recording {
+      if let Err(err) = recording.add_frame(&mut frame.to_vec()) {
+        log_error("recording.add_frame", err);
+        self.recording = None;
+      }
+    }
+  }
+
+  /// Draws the shapes on every visible layer into `pixels`, and into
+  /// `frame` as RGBA bytes, only visiting the tiles in `dirty_tiles`
+  ///
+  /// If `clear` is true, the tiles are cleared to the background
+  /// color before the shapes are drawn
+  fn rasterize(&self, pixels: &mut [Color], frame: &mut [u8], dirty_tiles: &[bool], clear: bool) {
+    let width = self.width as usize;
+    let tiles_wide = width.div_ceil(TILE_SIZE);
+    let tiles_high = (self.height as usize).div_ceil(TILE_SIZE);
+
+    let layers: Vec<&Layer> = self
+      .layers
+      .values()
+      .filter(|layer| layer.visible && layer.opacity > 0.0)
+      .collect();
+    let clear_color = if clear {
+      Some(self.background_color)
+    } else {
+      None
+    };
+    let layers = &layers;
+    let quality = self.quality;
+    let bands = Mutex::new(
+      pixels
+        .chunks_mut(width * TILE_SIZE)
+        .zip(frame.chunks_mut(width * TILE_SIZE * 4))
+        .zip(dirty_tiles.chunks(tiles_wide))
+        .enumerate(),
+    );
+    let threads = self.threads.min(tiles_high);
+
+    thread::scope(|scope| {
+      for _ in 0..threads {
+        scope.spawn(|| loop {
+          let next = bands.lock().unwrap().next();
+          let Some((i, ((pixels, frame), dirty))) = next else {
+            break;
+          };
+
+          let band_y = (i * TILE_SIZE) as i32;
+          let band_height = (pixels.len() / width) as i32;
+          let mut layer_pixels = Vec::<Color>::new();
+          for (start, end) in dirty_runs(dirty) {
+            let left = (start * TILE_SIZE) as i32;
+            let right = (end * TILE_SIZE).min(width) as i32;
+            let region = Bounds::new(left, band_y, right - left, band_height);
+
+            if let Some(color) = clear_color {
+              for row in pixels.chunks_exact_mut(width) {
+                row[left as usize..right as usize].fill(color);
+              }
+            }
+
+            for layer in layers.iter() {
+              if layer.opacity >= 1.0 {
+                for object in layer.objects.iter() {
+                  draw_object(pixels, width, band_y, object, &region, quality);
+                }
+                continue;
+              }
+
+              // translucent layers are drawn on their own and then blended
+              // as a whole, so their shapes don't show through each other
+              let transparent = Color::from_rgba(0.0, 0.0, 0.0, 0.0);
+              if layer_pixels.len() != pixels.len() {
+                layer_pixels = vec![transparent; pixels.len()];
+              }
+              for row in layer_pixels.chunks_exact_mut(width) {
+                row[left as usize..right as usize].fill(transparent);
+              }
+
+              for object in layer.objects.iter() {
+                draw_object(&mut layer_pixels, width, band_y, object, &region, quality);
+              }
+
+              let layer_rows = layer_pixels.chunks_exact(width);
+              for (row, layer_row) in pixels.chunks_exact_mut(width).zip(layer_rows) {
+                for x in left as usize..right as usize {
+                  row[x] = layer_row[x].scale_alpha(layer.opacity).over(row[x]);
+                }
+              }
+            }
+          }
+
+          for (pixel, color) in frame.chunks_exact_mut(4).zip(pixels.iter()) {
+            pixel.copy_from_slice(&color.as_bytes());
+          }
+        });
+      }
+    });
+  }
+
+  /// Forgets the shapes, clips and transforms of the frame that was just
+  /// drawn, ready for the next frame
+  fn end_frame(&mut self) {
+    for layer in self.layers.values_mut() {
+      layer.objects = Vec::<DrawCommand>::new();
+    }
+    self.clips = Vec::<Arc<Clip>>::new();
+    self.reset_transform();
+    self.transforms = Vec::<Transform>::new();
+    self.camera_enabled = true;
+  }
+
+  /// Saves the most recently drawn frame as a PNG file at `path`
+  ///
+  /// The image is saved at the size of the canvas, without the window
+  /// around it. Shapes drawn during the current frame are not drawn until
+  /// the frame ends, so calling this from [`Run::draw()`](crate::Run::draw)
+  /// saves the frame that is currently on the screen
+  ///
+  /// # Errors
+  /// returns an error if the file can't be created or written to
+  ///
+  /// # Examples
+  /// ```
+  /// use waow::*;
+  ///
+  /// struct App {}
+  /// impl Run for App {
+  ///   fn start(&mut self, _canvas: &mut Canvas) {}
+  ///   fn draw(&mut self, canvas: &mut Canvas, _input: &Input) {
+  ///     canvas.draw_square(10.0, 10.0, 30.0, Color::from_rgba(0.0, 1.0, 0.5, 1.0), None);
+  ///   }
+  /// }
+  ///
+  /// let mut headless = Headless::new(App {}, CanvasConfiguration::default());
+  /// headless.step();
+  ///
+  /// let path = std::env::temp_dir().join("waow_square.png");
+  /// headless.get_canvas().save_png(&path).unwrap();
+  /// # std::fs::remove_file(path).unwrap();
+  /// ```
+  pub fn save_png(&self, path: impl AsRef<Path>) -> io::Result<()> {
+    let file = File::create(path)?;
+    let mut encoder = png::Encoder::new(BufWriter::new(file), self.width, self.height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&self.get_frame_bytes())?;
+    return Ok(());
+  }
+
+  /// Starts recording every frame drawn into an animated GIF at `path`
+  ///
+  /// Each frame is shown for `frame_delay`. GIFs time frames in whole
+  /// hundredths of a second, so the time that doesn't fit is carried over
+  /// to later frames, keeping the recording at the right speed overall.
+  /// Every frame is shown for at least a hundredth of a second
+  ///
+  /// Frames are reduced to at most 256 colors each. The GIF is not
+  /// finished until [`Canvas::stop_recording()`] is called, and any
+  /// recording that was already in progress is stopped first
+  ///
+  /// # Errors
+  /// returns an error if the file can't be created, or the canvas is
+  /// larger than `65535` x `65535` pixels
+  ///
+  /// # Examples
+  /// ```
+  /// use std::time::Duration;
+  /// use waow::*;
+  ///
+  /// struct App {
+  ///   x: f32,
+  /// }
+  ///
+  /// impl Run for App {
+  ///   fn start(&mut self, _canvas: &mut Canvas) {}
+  ///   fn draw(&mut self, canvas: &mut Canvas, _input: &Input) {
+  ///     self.x += 4.0;
+  ///     canvas.draw_square(self.x, 10.0, 10.0, Color::from_rgba(1.0, 0.5, 0.0, 1.0), None);
+  ///   }
+  /// }
+  ///
+  /// let path = std::env::temp_dir().join("waow_moving_square.gif");
+  /// let config = CanvasConfiguration {
+  ///   width: 100,
+  ///   height: 30,
+  ///   ..Default::default()
+  /// };
+  ///
+  /// let mut headless = Headless::new(App { x: 0.0 }, config);
+  /// headless
+  ///   .get_canvas()
+  ///   .start_recording(&path, Duration::from_millis(50))
+  ///   .unwrap();
+  /// headless.run(20);
+  /// headless.get_canvas().stop_recording().unwrap();
+  /// # std::fs::remove_file(path).unwrap();
+  /// ```
+  ///
+  /// At 60 fps, six frames last a tenth of a second
+  /// ```
+  /// use std::{fs::File, time::Duration};
+  /// use waow::*;
+  ///
+  /// let path = std::env::temp_dir().join("waow_60fps.gif");
+  /// let mut canvas = Canvas::new_offscreen(10, 10);
+  /// canvas.start_recording(&path, Duration::from_secs(1) / 60).unwrap();
+  /// let mut frame = vec![0; 10 * 10 * 4];
+  /// for _ in 0..6 {
+  ///   canvas.draw_to_buffer(&mut frame);
+  /// }
+  /// canvas.stop_recording().unwrap();
+  ///
+  /// let mut decoder = gif::DecodeOptions::new().read_info(File::open(&path).unwrap()).unwrap();
+  /// let mut delays = Vec::new();
+  /// while let Some(frame) = decoder.read_next_frame().unwrap() {
+  ///   delays.push(frame.delay);
+  /// }
+  /// assert_eq!(delays, [2, 1, 2, 2, 1, 2]);
+  /// # std::fs::remove_file(path).unwrap();
+  /// ```
+  pub fn start_recording(&mut self, path: impl AsRef<Path>, frame_delay: Duration) -> io::Result<()> {
+    self.stop_recording()?;
+    self.recording = Some(Recording::new(
+      path,
+      self.width,
+      self.height,
+      frame_delay,
+    )?);
+    return Ok(());
+  }
+
+  /// Stops recording and finishes writing the GIF
+  ///
+  /// Does nothing if the canvas is not recording
+  ///
+  /// # Errors
+  /// returns an error if the end of the GIF can't be written
+  pub fn stop_recording(&mut self) -> io::Result<()> {
+    return match self.recording.take() {
+      Some(recording) => recording.finish(),
+      None => Ok(()),
+    };
+  }
+
+  /// Checks whether frames are currently being recorded
+  pub fn is_recording(&self) -> bool {
+    return self.recording.is_some();
+  }
+
+  /// Gets the most recently drawn frame as RGBA bytes, row by row
+  ///
+  /// returns a frame filled with the background color if nothing
+  /// has been drawn yet
+  pub(crate) fn get_frame_bytes(&self) -> Vec<u8> {
+    let size = (self.width * self.height) as usize;
+    if self.pixels.len() != size {
+      return self.background_color.as_bytes().repeat(size);
+    }
+
+    return self.pixels.iter().flat_map(|color| color.as_bytes()).collect();
+  }
+
+  /// Draws every shape drawn so far into a new [`Image`]
+  ///
+  /// The image is the same size as the canvas, with its top left
+  /// corner at (`x`, `y`). The shapes are drawn the same way as at the end
+  /// of the frame, but are kept, so this works on any canvas without
+  /// changing what is drawn to the screen. It is mostly useful for
+  /// canvases created with [`Canvas::new_offscreen()`]
+  ///
+  /// # Examples
+  /// ```
+  /// use waow::*;
+  ///
+  /// let mut offscreen = Canvas::new_offscreen(20, 20);
+  /// offscreen.draw_rect(0.0, 0.0, 10.0, 20.0, Color::from_rgba(1.0, 0.0, 0.0, 1.0), None);
+  ///
+  /// let image = offscreen.to_image(50.0, 50.0);
+  /// assert_eq!(image.get_pixel(5, 5).as_bytes(), [255, 0, 0, 255]);
+  /// assert_eq!(image.get_pixel(15, 5).as_bytes(), [0, 0, 0, 0]);
+  ///
+  /// // the rectangle is still drawn at the end of the frame
+  /// let mut frame = vec![0; 20 * 20 * 4];
+  /// offscreen.draw_to_buffer(&mut frame);
+  /// assert_eq!(frame[..4], [255, 0, 0, 255]);
+  /// ```
+  pub fn to_image(&self, x: f32, y: f32) -> Image {
+    let (width, height) = (self.width as usize, self.height as usize);
+    let mut image = Image::new(x, y, self.width, self.height);
+    if width == 0 || height == 0 {
+      return image;
+    }
+
+    let mut pixels = if self.pixels.len() == width * height {
+      self.pixels.clone()
+    } else {
+      vec![self.background_color; width * height]
+    };
+    let mut frame = vec![0; width * height * 4];
+    let dirty_tiles = vec![true; width.div_ceil(TILE_SIZE) * height.div_ceil(TILE_SIZE)];
+    self.rasterize(&mut pixels, &mut frame, &dirty_tiles, !self.persistent);
+
+    for (i, color) in pixels.iter().enumerate() {
+      let px = i as u32 % self.width;
+      let py = i as u32 / self.width;
+      image.set_pixel(px, py, *color);
+    }
+
+    return image;
+  }
+
+  /// Sets whether the canvas keeps what was drawn in previous frames
+  ///
+  /// When persistent, shapes are drawn on top of the last frame
+  /// instead of a fresh background, so drawings build up over time.
+  /// Use [`Canvas::clear()`] to start over
+  ///
+  /// # Examples
+  /// ```
+  /// use waow::*;
+  ///
+  /// struct App {
+  ///   x: f32,
+  /// }
+  ///
+  /// impl Run for App {
+  ///   fn start(&mut self, canvas: &mut Canvas) {
+  ///     canvas.set_persistent(true);
+  ///   }
+  ///
+  ///   fn draw(&mut self, canvas: &mut Canvas, _input: &Input) {
+  ///     // leaves a trail of squares behind
+  ///     self.x += 1.0;
+  ///     canvas.draw_square(self.x, 50.0, 10.0, Color::from_rgba(1.0, 1.0, 1.0, 1.0), None);
+  ///   }
+  /// }
+  /// ```
+  ///
+  /// Turning persistence off clears everything that built up
+  /// ```
+  /// use waow::*;
+  ///
+  /// struct App {
+  ///   x: f32,
+  /// }
+  ///
+  /// impl Run for App {
+  ///   fn start(&mut self, canvas: &mut Canvas) {
+  ///     canvas.set_persistent(true);
+  ///   }
+  ///
+  ///   fn draw(&mut self, canvas: &mut Canvas, _input: &Input) {
+  ///     if self.x < 200.0 {
+  ///       canvas.draw_square(self.x, 0.0, 10.0, Color::from_rgba(1.0, 1.0, 1.0, 1.0), None);
+  ///       self.x += 100.0;
+  ///     } else {
+  ///       canvas.set_persistent(false);
+  ///     }
+  ///   }
+  /// }
+  ///
+  /// let mut headless = Headless::new(App { x: 0.0 }, CanvasConfiguration::default());
+  /// headless.run(2);
+  /// assert_eq!(headless.get_frame()[..4], [255, 255, 255, 255]);
+  /// assert_eq!(headless.get_frame()[100 * 4..][..4], [255, 255, 255, 255]);
+  ///
+  /// headless.step();
+  /// assert_eq!(headless.get_frame()[..4], [0, 0, 0, 255]);
+  /// assert_eq!(headless.get_frame()[100 * 4..][..4], [0, 0, 0, 255]);
+  /// ```
+  pub fn set_persistent(&mut self, persistent: bool) {
+    // whatever built up while persistent needs to be cleared away
+    if self.persistent && !persistent {
+      self.redraw_all = true;
+    }
+    self.persistent = persistent;
+  }
+
+  /// Checks whether the canvas keeps what was drawn in previous frames
+  pub fn is_persistent(&self) -> bool {
+    return self.persistent;
+  }
+
+  /// Clears the canvas to the background color
+  ///
+  /// Removes everything drawn so far, including shapes drawn earlier
+  /// in the current frame. Mostly useful for persistent canvases,
+  /// see [`Canvas::set_persistent()`]
+  pub fn clear(&mut self) {
+    self.pixels.fill(self.background_color);
+    for layer in self.layers.values_mut() {
+      layer.objects = Vec::<DrawCommand>::new();
+    }
+  }
+
+  /// Sets how smoothly the edges of shapes are drawn
+  ///
+  /// # Examples
+  /// ```
+  /// use waow::*;
+  ///
+  /// struct App {}
+  /// impl Run for App {
+  ///   fn start(&mut self, canvas: &mut Canvas) {
+  ///     canvas.set_quality(RenderQuality::Antialiased);
+  ///   }
+  ///
+  ///   fn draw(&mut self, canvas: &mut Canvas, _input: &Input) {
+  ///     canvas.draw_shape(&shapes::Circle::new(50.0, 50.0, 30.0).with_fill(Color::from_rgba(1.0, 1.0, 1.0, 1.0)));
+  ///   }
+  /// }
+  /// ```
+  pub fn set_quality(&mut self, quality: RenderQuality) {
+    self.quality = quality;
+  }
+
+  /// Gets how smoothly the edges of shapes are drawn
+  pub fn get_quality(&self) -> RenderQuality {
+    return self.quality;
+  }
+
+  /// Sets the [`BlendMode`] used for every shape drawn after this call
+  ///
+  /// The blend mode stays set across frames until it is changed again
+  ///
+  /// # Examples
+  /// ```
+  /// use waow::*;
+  ///
+  /// struct App {}
+  /// impl Run for App {
+  ///   fn start(&mut self, _canvas: &mut Canvas) {}
+  ///   fn draw(&mut self, canvas: &mut Canvas, _input: &Input) {
+  ///     canvas.set_blend_mode(BlendMode::Screen);
+  ///     canvas.draw_square(10.0, 10.0, 30.0, Color::from_rgba(0.0, 0.5, 1.0, 1.0), None);
+  ///     canvas.set_blend_mode(BlendMode::Normal);
+  ///   }
+  /// }
+  /// ```
+  pub fn set_blend_mode(&mut self, mode: BlendMode) {
+    self.blend_mode = mode;
+  }
+
+  /// Gets the [`BlendMode`] that shapes are currently drawn with
+  pub fn get_blend_mode(&self) -> BlendMode {
+    return self.blend_mode;
+  }
+
+  /// Sets the layer that every shape drawn after this call is drawn on
+  ///
+  /// Layers are drawn from the lowest number to the highest, so shapes
+  /// on higher layers are drawn on top no matter when they were drawn.
+  /// Shapes are drawn on layer `0` by default, and the layer stays set
+  /// across frames until it is changed again
+  ///
+  /// # Examples
+  /// ```
+  /// use waow::*;
+  ///
+  /// const WORLD: i32 = 0;
+  /// const UI: i32 = 1;
+  ///
+  /// struct App {}
+  /// impl Run for App {
+  ///   fn start(&mut self, _canvas: &mut Canvas) {}
+  ///   fn draw(&mut self, canvas: &mut Canvas, _input: &Input) {
+  ///     // the health bar is drawn over the player even though it is drawn first
+  ///     canvas.set_layer(UI);
+  ///     canvas.draw_rect(5.0, 5.0, 50.0, 8.0, Color::from_rgba(1.0, 0.0, 0.0, 1.0), None);
+  ///
+  ///     canvas.set_layer(WORLD);
+  ///     canvas.draw_square(0.0, 0.0, 40.0, Color::from_rgba(0.0, 0.0, 1.0, 1.0), None);
+  ///   }
+  /// }
+  ///
+  /// let mut headless = Headless::new(App {}, CanvasConfiguration::default());
+  /// headless.step();
+  ///
+  /// let width = headless.get_canvas().get_size().0 as usize;
+  /// let frame = headless.get_frame();
+  /// assert_eq!(frame[(10 * width + 10) * 4..][..4], [255, 0, 0, 255]);
+  /// assert_eq!(frame[(30 * width + 10) * 4..][..4], [0, 0, 255, 255]);
+  /// ```
+  pub fn set_layer(&mut self, layer: i32) {
+    self.layer = layer;
+  }
 
-use crate::{
-  shapes::{Drawable, Image, Rectangle},
-  CanvasConfiguration, Color,
-};
+  /// Gets the layer that shapes are currently drawn on
+  pub fn get_layer(&self) -> i32 {
+    return self.layer;
+  }
 
-pub struct Canvas {
-  width: u32,
-  height: u32,
-  background_color: Color,
-  objects: Vec<Box<dyn Drawable>>,
-}
+  /// Shows or hides everything drawn on `layer`
+  ///
+  /// # Examples
+  /// ```
+  /// use waow::*;
+  ///
+  /// struct App {}
+  /// impl Run for App {
+  ///   fn start(&mut self, _canvas: &mut Canvas) {}
+  ///   fn draw(&mut self, canvas: &mut Canvas, input: &Input) {
+  ///     canvas.set_layer_visible(1, !input.key_held(KeyCode::Tab));
+  ///   }
+  /// }
+  /// ```
+  ///
+  /// Hiding a layer clears what it drew
+  /// ```
+  /// use waow::*;
+  ///
+  /// struct App {}
+  /// impl Run for App {
+  ///   fn start(&mut self, _canvas: &mut Canvas) {}
+  ///   fn draw(&mut self, canvas: &mut Canvas, _input: &Input) {
+  ///     canvas.set_layer(1);
+  ///     canvas.draw_square(0.0, 0.0, 10.0, Color::from_rgba(1.0, 1.0, 1.0, 1.0), None);
+  ///   }
+  /// }
+  ///
+  /// let mut headless = Headless::new(App {}, CanvasConfiguration::default());
+  /// headless.step();
+  /// assert_eq!(headless.get_frame()[..4], [255, 255, 255, 255]);
+  ///
+  /// headless.get_canvas().set_layer_visible(1, false);
+  /// headless.step();
+  /// assert_eq!(headless.get_frame()[..4], [0, 0, 0, 255]);
+  /// ```
+  pub fn set_layer_visible(&mut self, layer: i32, visible: bool) {
+    self.layers.entry(layer).or_insert_with(Layer::new).visible = visible;
+  }
 
-impl Canvas {
-  pub fn new(config: &CanvasConfiguration) -> Self {
-    return Self {
-      width: config.width,
-      height: config.height,
-      background_color: config.background_color,
-      objects: Vec::<Box<dyn Drawable>>::new(),
-    };
+  /// Checks whether `layer` is shown
+  pub fn is_layer_visible(&self, layer: i32) -> bool {
+    return self.layers.get(&layer).is_none_or(|layer| layer.visible);
   }
 
-  /// Gets the (width, height) of the canvas in pixels
-  pub fn get_size(&self) -> (u32, u32) {
-    return (self.width, self.height);
+  /// Sets the opacity of `layer` from `0.0` to `1.0`
+  ///
+  /// The shapes on the layer are drawn together first, and then blended
+  /// onto the layers below with the opacity, so overlapping shapes on the
+  /// same layer don't show through each other
+  ///
+  /// # Examples
+  /// ```
+  /// use waow::*;
+  ///
+  /// struct App {}
+  /// impl Run for App {
+  ///   fn start(&mut self, canvas: &mut Canvas) {
+  ///     canvas.set_layer_opacity(1, 0.5);
+  ///   }
+  ///
+  ///   fn draw(&mut self, canvas: &mut Canvas, _input: &Input) {
+  ///     canvas.set_layer(1);
+  ///     let white = Color::from_rgba(1.0, 1.0, 1.0, 1.0);
+  ///     canvas.draw_square(0.0, 0.0, 20.0, white, None);
+  ///     canvas.draw_square(10.0, 10.0, 20.0, white, None);
+  ///   }
+  /// }
+  ///
+  /// let mut headless = Headless::new(App {}, CanvasConfiguration::default());
+  /// headless.step();
+  ///
+  /// // the overlap is no brighter than the rest of the layer
+  /// let width = headless.get_canvas().get_size().0 as usize;
+  /// let frame = headless.get_frame();
+  /// assert_eq!(frame[(5 * width + 5) * 4], 127);
+  /// assert_eq!(frame[(15 * width + 15) * 4], 127);
+  /// ```
+  ///
+  /// # Panics
+  /// panics if `opacity` is not within the range `0.0` to `1.0`
+  pub fn set_layer_opacity(&mut self, layer: i32, opacity: f64) {
+    assert!(
+      (0.0..=1.0).contains(&opacity),
+      "opacity must be within the range 0.0 - 1.0"
+    );
+    self.layers.entry(layer).or_insert_with(Layer::new).opacity = opacity;
   }
 
-  pub fn draw_to_buffer(&mut self, frame: &mut [u8]) {
-    for (i, pixel) in frame.chunks_exact_mut(4).enumerate() {
-      let x = (i % self.width as usize) as i16;
-      let y = (i / self.height as usize) as i16;
-
-      let mut rgba = self.background_color;
-      for object in self.objects.iter() {
-        if let Some(color) = object.as_ref().get_color(x, y) {
-          rgba = color;
+  /// Gets the opacity of `layer`
+  pub fn get_layer_opacity(&self, layer: i32) -> f64 {
+    return self.layers.get(&layer).map_or(1.0, |layer| layer.opacity);
+  }
+
+  /// Limits every shape drawn after this call to the given rectangle
+  ///
+  /// Clips can be nested, with shapes limited to the area inside of every
+  /// clip that has been pushed. The clip lasts until [`Canvas::pop_clip()`]
+  /// is called, or the end of the frame. The rectangle is moved by the
+  /// current transform and the camera, see [`Canvas::translate()`]
+  ///
+  /// # Examples
+  /// ```
+  /// use waow::*;
+  ///
+  /// struct App {}
+  /// impl Run for App {
+  ///   fn start(&mut self, _canvas: &mut Canvas) {}
+  ///   fn draw(&mut self, canvas: &mut Canvas, _input: &Input) {
+  ///     // only the top left quarter of the circle is drawn
+  ///     canvas.push_clip_rect(0.0, 0.0, 50.0, 50.0);
+  ///     canvas.draw_shape(&shapes::Circle::new(50.0, 50.0, 40.0).with_fill(Color::from_rgba(1.0, 1.0, 0.0, 1.0)));
+  ///     canvas.pop_clip();
+  ///   }
+  /// }
+  ///
+  /// let mut headless = Headless::new(App {}, CanvasConfiguration::default());
+  /// headless.step();
+  ///
+  /// let width = headless.get_canvas().get_size().0 as usize;
+  /// let frame = headless.get_frame();
+  /// assert_eq!(frame[(40 * width + 40) * 4..][..4], [255, 255, 0, 255]);
+  /// assert_eq!(frame[(40 * width + 60) * 4..][..4], [0, 0, 0, 255]);
+  /// ```
+  pub fn push_clip_rect(&mut self, x: f32, y: f32, width: f32, height: f32) {
+    if !self.current_transform().is_identity() {
+      let rect = Rectangle::new(x, y, width, height).with_fill(Color::from_rgba(1.0, 1.0, 1.0, 1.0));
+      self.push_clip_shape(&rect);
+      return;
+    }
+
+    // the pixels whose centers are inside of the rectangle
+    let (left, top) = (
+      clamp_coordinate(x - 0.5).ceil() as i32,
+      clamp_coordinate(y - 0.5).ceil() as i32,
+    );
+    let (right, bottom) = (
+      clamp_coordinate(x + width - 0.5).ceil() as i32,
+      clamp_coordinate(y + height - 0.5).ceil() as i32,
+    );
+
+    let mut clip = self.current_clip();
+    clip.intersect(&Bounds::new(left, top, right - left, bottom - top));
+    self.clips.push(Arc::new(clip));
+  }
+
+  /// Limits every shape drawn after this call to the pixels that `shape`
+  /// draws to
+  ///
+  /// The alpha of the clip shape's color is multiplied into the shapes
+  /// drawn inside of it, so the clip can have soft edges. Works like
+  /// [`Canvas::push_clip_rect()`] otherwise
+  ///
+  /// # Examples
+  /// ```
+  /// use waow::*;
+  ///
+  /// struct App {}
+  /// impl Run for App {
+  ///   fn start(&mut self, _canvas: &mut Canvas) {}
+  ///   fn draw(&mut self, canvas: &mut Canvas, _input: &Input) {
+  ///     // a round window onto a striped pattern
+  ///     let window = shapes::Circle::new(50.0, 50.0, 30.0).with_fill(Color::from_rgba(1.0, 1.0, 1.0, 1.0));
+  ///     canvas.push_clip_shape(&window);
+  ///     for i in 0..10 {
+  ///       canvas.draw_rect(i as f32 * 10.0, 0.0, 5.0, 100.0, Color::from_rgba(0.0, 1.0, 0.5, 1.0), None);
+  ///     }
+  ///     canvas.pop_clip();
+  ///   }
+  /// }
+  ///
+  /// let mut headless = Headless::new(App {}, CanvasConfiguration::default());
+  /// headless.step();
+  ///
+  /// let width = headless.get_canvas().get_size().0 as usize;
+  /// let frame = headless.get_frame();
+  /// assert_eq!(frame[(50 * width + 50) * 4..][..4], [0, 255, 127, 255]);
+  /// assert_eq!(frame[(5 * width + 50) * 4..][..4], [0, 0, 0, 255]);
+  /// ```
+  pub fn push_clip_shape(&mut self, shape: &(impl Drawable + Clone + 'static)) {
+    let mut clip = self.current_clip();
+    match self.apply_transform(shape) {
+      Some(shape) => {
+        if let Some(bounds) = shape.get_bounds() {
+          clip.intersect(&bounds);
         }
+        clip.shapes.push(Arc::from(shape));
       }
+      None => clip.intersect(&Bounds::new(0, 0, 0, 0)),
+    }
+    self.clips.push(Arc::new(clip));
+  }
+
+  /// Removes the most recently pushed clip
+  ///
+  /// Does nothing if there are no clips
+  pub fn pop_clip(&mut self) {
+    self.clips.pop();
+  }
+
+  /// Saves the current transform, so it can be restored with [`Canvas::pop()`]
+  ///
+  /// # Examples
+  /// ```
+  /// use waow::*;
+  ///
+  /// struct App {}
+  /// impl Run for App {
+  ///   fn start(&mut self, _canvas: &mut Canvas) {}
+  ///   fn draw(&mut self, canvas: &mut Canvas, _input: &Input) {
+  ///     let petal = shapes::Rectangle::new(0.0, -5.0, 40.0, 10.0).with_fill(Color::from_rgba(1.0, 0.5, 0.8, 1.0));
+  ///
+  ///     canvas.translate(100.0, 100.0);
+  ///     for i in 0..8 {
+  ///       canvas.push();
+  ///       canvas.rotate(i as f32 * std::f32::consts::PI / 4.0);
+  ///       canvas.draw_shape(&petal);
+  ///       canvas.pop();
+  ///     }
+  ///   }
+  /// }
+  /// ```
+  pub fn push(&mut self) {
+    self.transforms.push(self.transform);
+  }
+
+  /// Restores the transform saved by the most recent call to [`Canvas::push()`]
+  ///
+  /// Does nothing if there is no saved transform
+  pub fn pop(&mut self) {
+    if let Some(transform) = self.transforms.pop() {
+      self.transform = transform;
+    }
+  }
+
+  /// Moves every shape drawn after this call by `x` and `y`
+  ///
+  /// Like [`Canvas::rotate()`] and [`Canvas::scale()`], this changes
+  /// the current transform, which is applied to every shape drawn until
+  /// the end of the frame. Each change is applied to shapes before the
+  /// changes made earlier
+  ///
+  /// # Examples
+  /// ```
+  /// use waow::*;
+  ///
+  /// struct App {}
+  /// impl Run for App {
+  ///   fn start(&mut self, _canvas: &mut Canvas) {}
+  ///   fn draw(&mut self, canvas: &mut Canvas, _input: &Input) {
+  ///     // a square spinning around its center at (50, 50)
+  ///     canvas.translate(50.0, 50.0);
+  ///     canvas.rotate(0.5);
+  ///     canvas.draw_square(-10.0, -10.0, 20.0, Color::from_rgba(0.0, 1.0, 1.0, 1.0), None);
+  ///   }
+  /// }
+  /// ```
+  pub fn translate(&mut self, x: f32, y: f32) {
+    self.transform = self.transform.multiply(&Transform::translation(x, y));
+  }
+
+  /// Rotates every shape drawn after this call clockwise around the
+  /// origin by `angle` in radians, see [`Canvas::translate()`]
+  pub fn rotate(&mut self, angle: f32) {
+    self.transform = self.transform.multiply(&Transform::rotation(angle));
+  }
+
+  /// Scales every shape drawn after this call away from the origin by
+  /// `x` horizontally and `y` vertically, see [`Canvas::translate()`]
+  pub fn scale(&mut self, x: f32, y: f32) {
+    self.transform = self.transform.multiply(&Transform::scaling(x, y));
+  }
+
+  /// Resets the current transform so shapes are drawn where they are
+  pub fn reset_transform(&mut self) {
+    self.transform = Transform::identity();
+  }
+
+  /// Gets the transform that is applied to shapes when they are drawn
+  ///
+  /// This does not include the camera, see [`Camera::get_transform()`]
+  pub fn get_transform(&self) -> Transform {
+    return self.transform;
+  }
+
+  /// Sets the camera that maps shapes drawn in world coordinates
+  /// to pixels on the screen
+  ///
+  /// The camera is applied after the current transform, and is kept
+  /// between frames
+  ///
+  /// # Examples
+  /// ```
+  /// use waow::*;
+  ///
+  /// struct App {}
+  /// impl Run for App {
+  ///   fn start(&mut self, canvas: &mut Canvas) {
+  ///     // show the world from (-200, -200) at twice the size
+  ///     canvas.set_camera(Camera::new(-200.0, -200.0, 2.0));
+  ///   }
+  ///
+  ///   fn draw(&mut self, canvas: &mut Canvas, input: &Input) {
+  ///     canvas.zoom_camera_with_wheel(input);
+  ///     canvas.pan_camera_with_drag(input, 0);
+  ///
+  ///     // a square at the world origin
+  ///     canvas.draw_square(-10.0, -10.0, 20.0, Color::from_rgba(1.0, 0.5, 0.0, 1.0), None);
+  ///
+  ///     // a marker under the mouse, drawn in world coordinates
+  ///     if let Some((x, y)) = input.mouse() {
+  ///       let (x, y) = canvas.screen_to_world(x, y);
+  ///       canvas.draw_shape(&shapes::Circle::new(x, y, 2.0).with_fill(Color::from_rgba(1.0, 1.0, 1.0, 1.0)));
+  ///     }
+  ///   }
+  /// }
+  /// ```
+  ///
+  /// Zooming far into a large world only draws the part that is on the screen
+  /// ```
+  /// use waow::*;
+  ///
+  /// struct App {}
+  /// impl Run for App {
+  ///   fn start(&mut self, canvas: &mut Canvas) {
+  ///     canvas.set_camera(Camera::new(0.0, 0.0, 5000.0));
+  ///   }
+  ///
+  ///   fn draw(&mut self, canvas: &mut Canvas, _input: &Input) {
+  ///     canvas.draw_rect(-1e6, -1e6, 2e6, 2e6, Color::from_rgba(0.0, 1.0, 0.0, 1.0), None);
+  ///   }
+  /// }
+  ///
+  /// let mut headless = Headless::new(App {}, CanvasConfiguration::default());
+  /// headless.step();
+  /// assert_eq!(headless.get_frame()[..4], [0, 255, 0, 255]);
+  /// ```
+  pub fn set_camera(&mut self, camera: Camera) {
+    self.camera = camera;
+  }
+
+  /// Gets the camera that maps world coordinates to the screen
+  pub fn get_camera(&self) -> Camera {
+    return self.camera;
+  }
+
+  /// Sets whether the camera is applied to shapes drawn after this call
+  ///
+  /// Shapes drawn while the camera is disabled are drawn in screen
+  /// coordinates, which is useful for UI drawn on top of the world.
+  /// The camera is enabled again at the end of the frame
+  pub fn set_camera_enabled(&mut self, enabled: bool) {
+    self.camera_enabled = enabled;
+  }
+
+  /// Checks whether the camera is applied to shapes when they are drawn
+  pub fn is_camera_enabled(&self) -> bool {
+    return self.camera_enabled;
+  }
+
+  /// Converts a position on the screen in pixels, such as
+  /// [`Input::mouse()`], to a world position, see [`Camera::screen_to_world()`]
+  pub fn screen_to_world(&self, x: f32, y: f32) -> (f32, f32) {
+    return self.camera.screen_to_world(x, y);
+  }
+
+  /// Converts a world position to a position on the screen in pixels,
+  /// see [`Camera::world_to_screen()`]
+  pub fn world_to_screen(&self, x: f32, y: f32) -> (f32, f32) {
+    return self.camera.world_to_screen(x, y);
+  }
+
+  /// Zooms the camera in or out around the mouse when the mouse wheel
+  /// is scrolled, see [`Camera::zoom_with_wheel()`]
+  pub fn zoom_camera_with_wheel(&mut self, input: &Input) -> bool {
+    return self.camera.zoom_with_wheel(input);
+  }
+
+  /// Moves the camera along with the mouse while `button` is held,
+  /// see [`Camera::pan_with_drag()`]
+  pub fn pan_camera_with_drag(&mut self, input: &Input, button: usize) -> bool {
+    return self.camera.pan_with_drag(input, button);
+  }
+
+  /// The current transform combined with the camera, if it is enabled
+  fn current_transform(&self) -> Transform {
+    if !self.camera_enabled {
+      return self.transform;
+    }
+
+    return self.camera.get_transform().multiply(&self.transform);
+  }
 
-      pixel.copy_from_slice(&rgba.as_bytes());
+  /// Boxes `shape`, wrapping it in the current transform if it
+  /// moves the shape at all
+  ///
+  /// returns `None` if the transform flattens the shape to nothing
+  fn apply_transform(&self, shape: &(impl Drawable + Clone + 'static)) -> Option<Box<dyn Drawable>> {
+    let shape = Box::new(shape.clone());
+    let transform = self.current_transform();
+    if transform.is_identity() {
+      return Some(shape);
     }
 
-    self.objects = Vec::<Box<dyn Drawable>>::new();
+    return TransformedShape::new(shape, transform)
+      .map(|shape| Box::new(shape) as Box<dyn Drawable>);
+  }
+
+  fn current_clip(&self) -> Clip {
+    return match self.clips.last() {
+      Some(clip) => clip.as_ref().clone(),
+      None => Clip {
+        bounds: None,
+        shapes: Vec::<Arc<dyn Drawable>>::new(),
+      },
+    };
+  }
+
+  fn current_layer(&mut self) -> &mut Layer {
+    return self.layers.entry(self.layer).or_insert_with(Layer::new);
   }
 }
 
 impl Canvas {
   /// Draws an arbitrary shape to the canvas
   ///
+  /// The shape is moved by the current transform,
+  /// see [`Canvas::translate()`], and the camera,
+  /// see [`Canvas::set_camera()`], blended using the current blend mode,
+  /// see [`Canvas::set_blend_mode()`], drawn on the current layer,
+  /// see [`Canvas::set_layer()`], and limited to the current clip,
+  /// see [`Canvas::push_clip_rect()`]
+  ///
   /// See [`Drawable`] for examples
   pub fn draw_shape(&mut self, shape: &(impl Drawable + Clone + 'static)) {
-    self.objects.push(Box::new(shape.clone()));
+    if let Some(shape) = self.apply_transform(shape) {
+      let blend_mode = self.blend_mode;
+      let clip = self.clips.last().cloned();
+      self.current_layer().objects.push(DrawCommand {
+        shape,
+        blend_mode,
+        clip,
+      });
+    }
   }
 
   /// Draws a square with the specified position, size, and color
@@ -68,7 +1534,7 @@ impl Canvas {
   /// impl Run for App {
   ///   fn start(&mut self, _canvas: &mut Canvas) {}
   ///   fn draw(&mut self, canvas: &mut Canvas, _input: &Input) {
-  ///     canvas.draw_square(10, 10, 30, Color::from_rgba(0.0, 1.0, 0.5, 1.0), None);
+  ///     canvas.draw_square(10.0, 10.0, 30.0, Color::from_rgba(0.0, 1.0, 0.5, 1.0), None);
   ///   }
   /// }
   /// ```
@@ -81,22 +1547,22 @@ impl Canvas {
   ///   fn start(&mut self, _canvas: &mut Canvas) {}
   ///   fn draw(&mut self, canvas: &mut Canvas, _input: &Input) {
   ///     canvas.draw_square(
-  ///       10,
-  ///       10,
-  ///       30,
+  ///       10.0,
+  ///       10.0,
+  ///       30.0,
   ///       Color::from_rgba(0.0, 1.0, 0.5, 1.0),
-  ///       Some((Color::from_rgba(1.0, 1.0, 1.0, 1.0), 3)),
+  ///       Some((Color::from_rgba(1.0, 1.0, 1.0, 1.0), 3.0)),
   ///     );
   ///   }
   /// }
   /// ```
   pub fn draw_square(
     &mut self,
-    x: i16,
-    y: i16,
-    size: i16,
+    x: f32,
+    y: f32,
+    size: f32,
     fill: Color,
-    border: Option<(Color, i16)>,
+    border: Option<(Color, f32)>,
   ) {
     let square = if let Some((color, width)) = border {
       Rectangle::new(x, y, size, size)
@@ -119,7 +1585,7 @@ impl Canvas {
   /// impl Run for App {
   ///   fn start(&mut self, _canvas: &mut Canvas) {}
   ///   fn draw(&mut self, canvas: &mut Canvas, _input: &Input) {
-  ///     canvas.draw_rect(50, 10, 20, 50, Color::from_rgba(0.0, 0.5, 1.0, 1.0), None);
+  ///     canvas.draw_rect(50.0, 10.0, 20.0, 50.0, Color::from_rgba(0.0, 0.5, 1.0, 1.0), None);
   ///   }
   /// }
   /// ```
@@ -132,21 +1598,44 @@ impl Canvas {
   ///   fn start(&mut self, _canvas: &mut Canvas) {}
   ///   fn draw(&mut self, canvas: &mut Canvas, _input: &Input) {
   ///     canvas.draw_rect(
-  ///       10, 10, 30, 50,
+  ///       10.0, 10.0, 30.0, 50.0,
   ///       Color::from_rgba(0.0, 1.0, 0.5, 1.0),
-  ///       Some((Color::from_rgba(1.0, 1.0, 1.0, 1.0), 3)),
+  ///       Some((Color::from_rgba(1.0, 1.0, 1.0, 1.0), 3.0)),
   ///     );
   ///   }
   /// }
   /// ```
+  ///
+  /// Shapes can be far larger than the canvas, only the part
+  /// on the canvas is drawn
+  /// ```
+  /// use waow::*;
+  ///
+  /// struct App {}
+  /// impl Run for App {
+  ///   fn start(&mut self, _canvas: &mut Canvas) {}
+  ///   fn draw(&mut self, canvas: &mut Canvas, _input: &Input) {
+  ///     canvas.draw_shape(&shapes::Circle::new(0.0, 0.0, 3e9).with_fill(Color::from_rgba(1.0, 0.0, 0.0, 1.0)));
+  ///     canvas.draw_rect(-3e9, 0.0, 6e9, 10.0, Color::from_rgba(1.0, 1.0, 1.0, 1.0), None);
+  ///   }
+  /// }
+  ///
+  /// let mut headless = Headless::new(App {}, CanvasConfiguration::default());
+  /// headless.step();
+  ///
+  /// let width = headless.get_canvas().get_size().0 as usize;
+  /// let frame = headless.get_frame();
+  /// assert_eq!(frame[(5 * width + 50) * 4..][..4], [255, 255, 255, 255]);
+  /// assert_eq!(frame[(50 * width + 50) * 4..][..4], [255, 0, 0, 255]);
+  /// ```
   pub fn draw_rect(
     &mut self,
-    x: i16,
-    y: i16,
-    width: i16,
-    height: i16,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
     fill: Color,
-    border: Option<(Color, i16)>,
+    border: Option<(Color, f32)>,
   ) {
     let rect = if let Some((color, border_width)) = border {
       Rectangle::new(x, y, width, height)
@@ -169,7 +1658,7 @@ impl Canvas {
   /// impl Run for App {
   ///   fn start(&mut self, _canvas: &mut Canvas) {}
   ///   fn draw(&mut self, canvas: &mut Canvas, _input: &Input) {
-  ///     let img = shapes::Image::new(30, 30, 100, 100);
+  ///     let img = shapes::Image::new(30.0, 30.0, 100, 100);
   ///
   ///     canvas.draw_image(&img);
   ///   }
@@ -179,3 +1668,88 @@ impl Canvas {
     self.draw_shape(image);
   }
 }
+
+/// Composites a single object onto `pixels`, only visiting
+/// the pixels of its row spans that are within `region`
+///
+/// `pixels` holds full rows of the canvas, starting at row `pixels_y`
+fn draw_object(
+  pixels: &mut [Color],
+  width: usize,
+  pixels_y: i32,
+  object: &DrawCommand,
+  region: &Bounds,
+  quality: RenderQuality,
+) {
+  let clip_shapes = match &object.clip {
+    Some(clip) => clip.shapes.as_slice(),
+    None => &[],
+  };
+
+  if let Some(bounds) = object.get_bounds(region) {
+    for y in bounds.y..bounds.bottom() {
+      if let Some((start, end)) = object.shape.get_row_span(y) {
+        let row = (y - pixels_y) as usize * width;
+        'pixel: for x in start.max(bounds.x)..end.min(bounds.right()) {
+          if let Some(mut color) = get_pixel_color(object.shape.as_ref(), x, y, quality) {
+            for clip in clip_shapes.iter() {
+              match get_pixel_color(clip.as_ref(), x, y, quality) {
+                Some(mask) => color = color.scale_alpha(mask.get_alpha()),
+                None => continue 'pixel,
+              }
+            }
+
+            let pixel = &mut pixels[row + x as usize];
+            *pixel = color.blend(*pixel, object.blend_mode);
+          }
+        }
+      }
+    }
+  }
+}
+
+/// Gets the color of `shape` at the pixel at (`x`, `y`) with the given quality
+fn get_pixel_color(shape: &dyn Drawable, x: i32, y: i32, quality: RenderQuality) -> Option<Color> {
+  return match quality {
+    RenderQuality::Fast => shape.get_color(x, y),
+    RenderQuality::Antialiased => shape.get_color_antialiased(x, y),
+    RenderQuality::Supersampled(n) => supersample(shape, x, y, n),
+  };
+}
+
+/// Averages `n` x `n` evenly spaced samples of `shape` within the pixel at (`x`, `y`)
+pub(crate) fn supersample(shape: &dyn Drawable, x: i32, y: i32, n: u8) -> Option<Color> {
+  let n = n.max(1) as u32;
+  let step = 1.0 / n as f32;
+  let weight = 1.0 / (n * n) as f64;
+
+  return Color::from_coverage((0..n * n).map(|i| {
+    let sample_x = x as f32 + ((i % n) as f32 + 0.5) * step;
+    let sample_y = y as f32 + ((i / n) as f32 + 0.5) * step;
+    (shape.sample(sample_x, sample_y), weight)
+  }));
+}
+
+/// Finds each run of consecutive dirty tiles in a row of tiles,
+/// as the range of tile indices from `start` to `end` (exclusive)
+fn dirty_runs(tiles: &[bool]) -> Vec<(usize, usize)> {
+  let mut runs = Vec::<(usize, usize)>::new();
+  let mut start = None;
+
+  for (i, dirty) in tiles.iter().enumerate() {
+    match (start, *dirty) {
+      (None, true) => start = Some(i),
+      (Some(run_start), false) => {
+        runs.push((run_start, i));
+        start = None;
+      }
+      _ => {}
+    }
+  }
+
+  if let Some(run_start) = start {
+    runs.push((run_start, tiles.len()));
+  }
+
+  return runs;
+}
@@ -3,6 +3,8 @@
 //! There are multiple methods for creating colors, with
 //! different input types
 
+use crate::BlendMode;
+
 #[derive(Clone, Copy)]
 pub struct Color {
   r: f64,
@@ -26,19 +28,19 @@ impl Color {
   /// ```
   pub fn from_rgba(r: f64, g: f64, b: f64, a: f64) -> Self {
     assert!(
-      r <= 1.0 && r >= 0.0,
+      (0.0..=1.0).contains(&r),
       "color values must be within the range 0.0 - 1.0"
     );
     assert!(
-      g <= 1.0 && g >= 0.0,
+      (0.0..=1.0).contains(&g),
       "color values must be within the range 0.0 - 1.0"
     );
     assert!(
-      b <= 1.0 && b >= 0.0,
+      (0.0..=1.0).contains(&b),
       "color values must be within the range 0.0 - 1.0"
     );
     assert!(
-      a <= 1.0 && a >= 0.0,
+      (0.0..=1.0).contains(&a),
       "color values must be within the range 0.0 - 1.0"
     );
 
@@ -55,9 +57,10 @@ impl Color {
   /// use waow::*;
   ///
   /// let col = Color::from_rgba32(0.5, 0.75, 0.25, 1.0);
+  /// assert_eq!(Color::from_rgba32(1.0, 0.0, 0.0, 1.0).as_bytes(), [255, 0, 0, 255]);
   /// ```
   pub fn from_rgba32(r: f32, g: f32, b: f32, a: f32) -> Self {
-    return Self::from_rgba(r as f64, a as f64, b as f64, g as f64);
+    return Self::from_rgba(r as f64, g as f64, b as f64, a as f64);
   }
 
   /// Creates a color from rgba values from `0` to `255` as `u8`
@@ -89,4 +92,108 @@ impl Color {
       (self.a * 255.0) as u8,
     ];
   }
+
+  /// Combines colors that each cover part of a pixel into a single color
+  ///
+  /// `parts` holds each color, if any, along with the fraction of the pixel
+  /// it covers. returns `None` if nothing covers the pixel
+  pub(crate) fn from_coverage(
+    parts: impl IntoIterator<Item = (Option<Color>, f64)>,
+  ) -> Option<Color> {
+    let (mut r, mut g, mut b, mut a) = (0.0, 0.0, 0.0, 0.0);
+    for (color, coverage) in parts {
+      if let Some(color) = color {
+        let weight = color.a * coverage;
+        r += color.r * weight;
+        g += color.g * weight;
+        b += color.b * weight;
+        a += weight;
+      }
+    }
+
+    if a <= 0.0 {
+      return None;
+    }
+
+    return Some(Self {
+      r: r / a,
+      g: g / a,
+      b: b / a,
+      a: a.min(1.0),
+    });
+  }
+
+  /// returns the alpha of the color from `0.0` to `1.0`
+  pub(crate) fn get_alpha(&self) -> f64 {
+    return self.a;
+  }
+
+  /// returns the color with its alpha multiplied by `factor`
+  pub(crate) fn scale_alpha(&self, factor: f64) -> Color {
+    return Self {
+      a: self.a * factor,
+      ..*self
+    };
+  }
+
+  /// Composites this color on top of `dst` using source-over alpha blending
+  ///
+  /// The alpha of `self` controls how much of `dst` shows through
+  ///
+  /// # Examples
+  /// ```
+  /// use waow::*;
+  ///
+  /// let red = Color::from_rgba(1.0, 0.0, 0.0, 0.5);
+  /// let blue = Color::from_rgba(0.0, 0.0, 1.0, 1.0);
+  ///
+  /// assert_eq!(red.over(blue).as_bytes(), [127, 0, 127, 255]);
+  /// ```
+  pub fn over(&self, dst: Color) -> Color {
+    let a = self.a + dst.a * (1.0 - self.a);
+    if a <= 0.0 {
+      return Self {
+        r: 0.0,
+        g: 0.0,
+        b: 0.0,
+        a: 0.0,
+      };
+    }
+
+    let dst_weight = dst.a * (1.0 - self.a);
+    return Self {
+      r: (self.r * self.a + dst.r * dst_weight) / a,
+      g: (self.g * self.a + dst.g * dst_weight) / a,
+      b: (self.b * self.a + dst.b * dst_weight) / a,
+      a,
+    };
+  }
+
+  /// Composites this color on top of `dst` using the given [`BlendMode`]
+  ///
+  /// # Examples
+  /// ```
+  /// use waow::*;
+  ///
+  /// let red = Color::from_rgba(0.5, 0.0, 0.0, 1.0);
+  /// let grey = Color::from_rgba(0.25, 0.25, 0.25, 1.0);
+  ///
+  /// assert_eq!(red.blend(grey, BlendMode::Add).as_bytes(), [191, 63, 63, 255]);
+  /// assert_eq!(red.blend(grey, BlendMode::Multiply).as_bytes(), [31, 0, 0, 255]);
+  /// ```
+  pub fn blend(&self, dst: Color, mode: BlendMode) -> Color {
+    if mode == BlendMode::Normal {
+      return self.over(dst);
+    }
+
+    let mix = |d: f64, s: f64| (1.0 - dst.a) * s + dst.a * mode.blend_channel(d, s);
+    let mixed = Self {
+      r: mix(dst.r, self.r),
+      g: mix(dst.g, self.g),
+      b: mix(dst.b, self.b),
+      a: self.a,
+    };
+
+    return mixed.over(dst);
+  }
 }
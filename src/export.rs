@@ -0,0 +1,135 @@
+//! Exports the frames of an app to files on a fixed virtual clock
+//!
+//! Frames are drawn with a [`Headless`] runner, so every frame is
+//! exported no matter how long it takes to draw, and the app sees
+//! time passing at exactly the export frame rate
+
+use std::{
+  fs::{self, File},
+  io::{self, BufWriter, Write},
+  path::PathBuf,
+};
+
+use crate::{CanvasConfiguration, Headless, Run};
+
+/// The kinds of files that frames can be exported to
+pub enum ExportFormat {
+  /// Saves every frame as a numbered PNG file, `frame-00000.png`,
+  /// `frame-00001.png` and so on, in the given directory
+  PngSequence(PathBuf),
+  /// Writes every frame into a single uncompressed YUV4MPEG2 video
+  /// file at the given path, which can be converted with tools like ffmpeg
+  Y4m(PathBuf),
+}
+
+/// Holds the configuration of an export
+///
+/// Passed into the [`export()`] method to set how many frames
+/// are exported, how fast they play, and where they are saved
+pub struct ExportConfiguration {
+  pub frames: u64,
+  pub frame_rate: u32,
+  pub format: ExportFormat,
+}
+
+/// Runs an app without a window and exports every frame it draws
+///
+/// The app is drawn for the given number of frames on a virtual
+/// clock running at the export frame rate, see [`Headless`].
+/// Times on the canvas, such as [`Canvas::get_elapsed_time()`](crate::Canvas::get_elapsed_time),
+/// come from the virtual clock too
+///
+/// # Errors
+/// returns an error if any of the files can't be created or written to
+///
+/// # Examples
+/// ```
+/// use waow::*;
+///
+/// struct App {
+///   radius: f32,
+/// }
+///
+/// impl Run for App {
+///   fn start(&mut self, _canvas: &mut Canvas) {}
+///   fn draw(&mut self, canvas: &mut Canvas, _input: &Input) {
+///     self.radius += 1.0;
+///     canvas.draw_shape(
+///       &shapes::Circle::new(50.0, 50.0, self.radius).with_fill(Color::from_rgba(1.0, 1.0, 1.0, 1.0)),
+///     );
+///   }
+/// }
+///
+/// let path = std::env::temp_dir().join("waow_growing_circle.y4m");
+/// export(
+///   App { radius: 0.0 },
+///   CanvasConfiguration {
+///     width: 100,
+///     height: 100,
+///     ..Default::default()
+///   },
+///   ExportConfiguration {
+///     frames: 30,
+///     frame_rate: 30,
+///     format: ExportFormat::Y4m(path.clone()),
+///   },
+/// )
+/// .unwrap();
+/// # std::fs::remove_file(path).unwrap();
+/// ```
+pub fn export(
+  app: impl Run,
+  config: CanvasConfiguration,
+  export: ExportConfiguration,
+) -> io::Result<()> {
+  let (width, height) = (config.width, config.height);
+  let mut headless = Headless::new(app, config).with_frame_rate(export.frame_rate);
+
+  match export.format {
+    ExportFormat::PngSequence(directory) => {
+      fs::create_dir_all(&directory)?;
+      for i in 0..export.frames {
+        headless.step();
+        headless
+          .get_canvas()
+          .save_png(directory.join(format!("frame-{i:05}.png")))?;
+      }
+    }
+    ExportFormat::Y4m(path) => {
+      let mut video = BufWriter::new(File::create(path)?);
+      writeln!(
+        video,
+        "YUV4MPEG2 W{width} H{height} F{}:1 Ip A1:1 C444",
+        export.frame_rate
+      )?;
+
+      for _ in 0..export.frames {
+        headless.step();
+        write_y4m_frame(&mut video, headless.get_frame())?;
+      }
+      video.flush()?;
+    }
+  }
+
+  return Ok(());
+}
+
+/// Writes a frame of RGBA bytes as a Y4M frame with full resolution
+/// Y, Cb and Cr planes, using the BT.601 color space
+fn write_y4m_frame(video: &mut impl Write, rgba: &[u8]) -> io::Result<()> {
+  let size = rgba.len() / 4;
+  let mut planes = vec![0; size * 3];
+
+  for (i, pixel) in rgba.chunks_exact(4).enumerate() {
+    let r = pixel[0] as f64 / 255.0;
+    let g = pixel[1] as f64 / 255.0;
+    let b = pixel[2] as f64 / 255.0;
+
+    planes[i] = (16.0 + 65.481 * r + 128.553 * g + 24.966 * b).round() as u8;
+    planes[size + i] = (128.0 - 37.797 * r - 74.203 * g + 112.0 * b).round() as u8;
+    planes[size * 2 + i] = (128.0 + 112.0 * r - 93.786 * g - 18.214 * b).round() as u8;
+  }
+
+  video.write_all(b"FRAME\n")?;
+  return video.write_all(&planes);
+}
@@ -0,0 +1,40 @@
+//! Shows the window over the whole screen, see [`FullscreenMode`]
+
+use winit::{monitor::MonitorHandle, window::Fullscreen};
+
+/// How the window covers the screen when it is fullscreen
+///
+/// The canvas is fit into the screen the same way as a resized window,
+/// see [`ScalingMode`](crate::ScalingMode)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FullscreenMode {
+  /// Covers the screen with a window without borders, keeping
+  /// the resolution of the screen
+  #[default]
+  Borderless,
+  /// Takes over the screen, switching it to its highest resolution
+  /// and refresh rate. Falls back to [`FullscreenMode::Borderless`] if the
+  /// screen's resolutions can't be found
+  Exclusive,
+}
+
+impl FullscreenMode {
+  /// Gets the winit fullscreen setting for `monitor`, which is
+  /// the monitor the window is on
+  pub(crate) fn to_fullscreen(self, monitor: Option<MonitorHandle>) -> Fullscreen {
+    if self == FullscreenMode::Exclusive {
+      let video_mode = monitor.as_ref().and_then(|monitor| {
+        monitor.video_modes().max_by_key(|video_mode| {
+          let size = video_mode.size();
+          (size.width * size.height, video_mode.refresh_rate_millihertz())
+        })
+      });
+
+      if let Some(video_mode) = video_mode {
+        return Fullscreen::Exclusive(video_mode);
+      }
+    }
+
+    return Fullscreen::Borderless(monitor);
+  }
+}
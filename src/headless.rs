@@ -0,0 +1,241 @@
+//! Runs an app without opening a window
+//!
+//! Frames are drawn into a buffer in memory instead of on the screen,
+//! which allows apps to run on machines without a display, such as
+//! in automated tests or when rendering images in bulk
+//!
+//! Time is measured with a virtual clock that advances by a fixed
+//! amount every frame, no matter how long the frame took to draw
+
+use std::time::Duration;
+
+use crate::{timing::FixedTimestep, Canvas, CanvasConfiguration, Input, Run};
+
+/// The frame rate of the virtual clock unless one is given
+const DEFAULT_FRAME_RATE: u32 = 60;
+
+/// Runs an app for any number of frames without a window
+///
+/// The app is started when the runner is created, and drawn once
+/// every time [`Headless::step()`] is called. The input never
+/// receives any events
+///
+/// Each frame advances the virtual clock by one frame at the frame
+/// rate, which is 60 frames per second unless set with
+/// [`Headless::with_frame_rate()`]
+///
+/// # Examples
+/// ```
+/// use waow::*;
+///
+/// struct App {}
+/// impl Run for App {
+///   fn start(&mut self, _canvas: &mut Canvas) {}
+///   fn draw(&mut self, canvas: &mut Canvas, _input: &Input) {
+///     canvas.draw_square(0.0, 0.0, 10.0, Color::from_rgba(1.0, 0.0, 0.0, 1.0), None);
+///   }
+/// }
+///
+/// let mut headless = Headless::new(
+///   App {},
+///   CanvasConfiguration {
+///     width: 20,
+///     height: 20,
+///     ..Default::default()
+///   },
+/// );
+/// headless.run(3);
+///
+/// let frame = headless.get_frame();
+/// assert_eq!(headless.get_frame_count(), 3);
+/// assert_eq!(frame[0..4], [255, 0, 0, 255]);
+/// assert_eq!(frame[(20 * 15 + 15) * 4..(20 * 15 + 16) * 4], [0, 0, 0, 255]);
+/// ```
+pub struct Headless<T: Run> {
+  app: T,
+  canvas: Canvas,
+  input: Input,
+  frame: Vec<u8>,
+  frame_count: u64,
+  frame_rate: u32,
+  timestep: FixedTimestep,
+}
+
+impl<T: Run> Headless<T> {
+  /// Creates a canvas with the given [`CanvasConfiguration`]
+  /// and starts the app with it
+  ///
+  /// # Panics
+  /// panics if [`CanvasConfiguration::update_rate`] or
+  /// [`CanvasConfiguration::target_frame_rate`] is `0`
+  pub fn new(mut app: T, config: CanvasConfiguration) -> Self {
+    let timestep = FixedTimestep::new(config.update_rate);
+    let mut canvas = Canvas::new(&config);
+    app.start(&mut canvas);
+
+    return Self {
+      app,
+      canvas,
+      input: Input::new(),
+      frame: vec![0; (config.width * config.height * 4) as usize],
+      frame_count: 0,
+      frame_rate: DEFAULT_FRAME_RATE,
+      timestep,
+    };
+  }
+
+  /// A builder that sets the frame rate of the virtual clock
+  ///
+  /// # Panics
+  /// panics if `frame_rate` is `0`
+  ///
+  /// # Examples
+  /// ```
+  /// use std::time::Duration;
+  /// use waow::*;
+  ///
+  /// struct App {}
+  /// impl Run for App {
+  ///   fn start(&mut self, _canvas: &mut Canvas) {}
+  ///   fn draw(&mut self, _canvas: &mut Canvas, _input: &Input) {}
+  /// }
+  ///
+  /// let mut headless = Headless::new(App {}, CanvasConfiguration::default()).with_frame_rate(25);
+  /// headless.run(50);
+  ///
+  /// assert_eq!(headless.get_time(), Duration::from_secs(2));
+  /// ```
+  pub fn with_frame_rate(mut self, frame_rate: u32) -> Self {
+    assert!(frame_rate > 0, "frame rate must be greater than 0");
+    self.frame_rate = frame_rate;
+    return self;
+  }
+
+  /// Draws a single frame of the app, after updating it for
+  /// the time the frame takes on the virtual clock
+  ///
+  /// # Examples
+  /// ```
+  /// use waow::*;
+  ///
+  /// struct App {
+  ///   updates: u32,
+  /// }
+  ///
+  /// impl Run for App {
+  ///   fn start(&mut self, _canvas: &mut Canvas) {}
+  ///   fn update(&mut self, _canvas: &mut Canvas, _input: &Input, _delta_time: f32) {
+  ///     self.updates += 1;
+  ///   }
+  ///   fn draw(&mut self, _canvas: &mut Canvas, _input: &Input) {}
+  /// }
+  ///
+  /// let config = CanvasConfiguration {
+  ///   update_rate: 60,
+  ///   ..Default::default()
+  /// };
+  /// let mut headless = Headless::new(App { updates: 0 }, config).with_frame_rate(30);
+  /// headless.run(30);
+  ///
+  /// // one second has passed on the virtual clock
+  /// assert_eq!(headless.get_app().updates, 60);
+  /// ```
+  pub fn step(&mut self) {
+    self.input.step_with_window_events(&[]);
+
+    // measured from the start so rounding doesn't build up
+    let frame_time = Duration::from_secs(self.frame_count + 1) / self.frame_rate - self.get_time();
+    self.canvas.tick(frame_time);
+
+    let updates = self.timestep.advance(frame_time);
+    for _ in 0..updates {
+      let delta_time = self.timestep.get_delta_time();
+      self.app.update(&mut self.canvas, &self.input, delta_time);
+    }
+    self.canvas.set_interpolation(self.timestep.get_interpolation());
+
+    self.app.draw(&mut self.canvas, &self.input);
+    self.canvas.draw_to_buffer(&mut self.frame);
+    self.frame_count += 1;
+  }
+
+  /// Draws `frames` frames of the app
+  pub fn run(&mut self, frames: u64) {
+    for _ in 0..frames {
+      self.step();
+    }
+  }
+
+  /// Gets the most recently drawn frame as RGBA bytes, row by row
+  pub fn get_frame(&self) -> &[u8] {
+    return &self.frame;
+  }
+
+  /// Gets the number of frames drawn so far
+  pub fn get_frame_count(&self) -> u64 {
+    return self.frame_count;
+  }
+
+  /// Gets the frame rate of the virtual clock in frames per second
+  pub fn get_frame_rate(&self) -> u32 {
+    return self.frame_rate;
+  }
+
+  /// Gets the time on the virtual clock, which is the
+  /// number of frames drawn divided by the frame rate
+  pub fn get_time(&self) -> Duration {
+    return Duration::from_secs(self.frame_count) / self.frame_rate;
+  }
+
+  /// Gets the app being run
+  pub fn get_app(&mut self) -> &mut T {
+    return &mut self.app;
+  }
+
+  /// Gets the canvas the app draws to
+  pub fn get_canvas(&mut self) -> &mut Canvas {
+    return &mut self.canvas;
+  }
+
+  /// Stops running the app, returning it
+  pub fn into_app(self) -> T {
+    return self.app;
+  }
+}
+
+/// Runs an app for `frames` frames without opening a window
+///
+/// Works like [`create()`](crate::create), but returns the last frame
+/// as RGBA bytes, row by row, instead of showing it on the screen.
+/// See [`Headless`] for more control over each frame
+///
+/// # Examples
+/// ```
+/// use waow::*;
+///
+/// struct App {}
+/// impl Run for App {
+///   fn start(&mut self, _canvas: &mut Canvas) {}
+///   fn draw(&mut self, canvas: &mut Canvas, _input: &Input) {
+///     canvas.draw_square(0.0, 0.0, 10.0, Color::from_rgba(0.0, 1.0, 0.0, 1.0), None);
+///   }
+/// }
+///
+/// let frame = create_headless(
+///   App {},
+///   CanvasConfiguration {
+///     width: 40,
+///     height: 30,
+///     ..Default::default()
+///   },
+///   10,
+/// );
+///
+/// assert_eq!(frame.len(), 40 * 30 * 4);
+/// assert_eq!(frame[0..4], [0, 255, 0, 255]);
+/// ```
+pub fn create_headless(app: impl Run, config: CanvasConfiguration, frames: u64) -> Vec<u8> {
+  let mut headless = Headless::new(app, config);
+  headless.run(frames);
+  return headless.frame;
+}
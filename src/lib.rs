@@ -7,36 +7,82 @@
 //!
 //! For a quick start, see the [examples](https://github.com/samgcode/waow/tree/master/examples)
 
+#![allow(clippy::needless_return)]
+
+use std::{
+  collections::HashSet,
+  time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
 use error_iter::ErrorIter;
-use log::error;
-use pixels::{Pixels, SurfaceTexture};
+use log::{error, info};
+use pixels::{Pixels, PixelsBuilder, SurfaceTexture, TextureError};
 use winit::{
-  dpi::LogicalSize,
-  event::Event,
+  dpi::{LogicalSize, PhysicalPosition},
+  event::{ElementState, Event, KeyboardInput, MouseButton, MouseScrollDelta, WindowEvent},
   event_loop::{ControlFlow, EventLoop},
-  window::WindowBuilder,
+  window::{Window, WindowBuilder},
 };
 
+use scaling::Viewport;
+use timing::{FixedTimestep, MAX_FRAME_TIME};
+
+mod blend;
+mod camera;
 mod canvas;
 mod color;
+mod export;
+mod fullscreen;
+mod headless;
+mod recording;
+mod scaling;
 pub mod shapes;
+mod timing;
+mod transform;
 
+/// The way colors are combined with what is already on the canvas
+pub use blend::BlendMode;
+/// A view onto the world that can be panned and zoomed
+pub use camera::Camera;
 /// Canvas that can be drawn to and gets displayed on the screen
 pub use canvas::Canvas;
+/// How smoothly the edges of shapes are drawn
+pub use canvas::RenderQuality;
 /// represents an RGBA color
 pub use color::Color;
+/// Exports the frames of an app to image or video files
+pub use export::{export, ExportConfiguration, ExportFormat};
+/// How the window covers the screen when it is fullscreen
+pub use fullscreen::FullscreenMode;
+/// Runs an app without a window
+pub use headless::{create_headless, Headless};
+/// How the canvas is fit into a window of a different size
+pub use scaling::ScalingMode;
+/// A combination of translations, rotations and scales
+pub use transform::Transform;
 /// Represents an input key
 pub use winit::event::VirtualKeyCode as KeyCode;
 /// Handles user input events
 pub use winit_input_helper::WinitInputHelper as Input;
 
+/// How long each frame recorded with [`CanvasConfiguration::record_key`] is
+/// shown when the frame rate isn't known yet, see [`recording_frame_delay()`]
+const RECORDING_FRAME_DELAY: Duration = Duration::from_millis(30);
+
+/// How many pixels of scrolling on a touchpad count as one line of
+/// [`Run::mouse_wheel()`], the same as [`Input::scroll_diff()`]
+const PIXELS_PER_SCROLL_LINE: f64 = 38.0;
+
 /// Holds the configuration of the canvas.
 ///
 /// Passed into the [`create()`] method to set the various
 /// attributes of the canvas
 ///
+/// Any attributes that are not set can be filled in with
+/// `..Default::default()`
+///
 /// # Examples
-/// ```
+/// ```no_run
 /// use waow::*;
 ///
 /// let app = App {};
@@ -47,6 +93,7 @@ pub use winit_input_helper::WinitInputHelper as Input;
 ///     height: 400,
 ///     background_color: Color::from_rgba(0.0, 0.0, 0.0, 1.0),
 ///     window_name: String::from("waow!"),
+///     ..Default::default()
 ///   },
 /// );
 ///
@@ -61,12 +108,82 @@ pub struct CanvasConfiguration {
   pub height: u32,
   pub background_color: Color,
   pub window_name: String,
+  /// Keeps what was drawn in previous frames instead of clearing
+  /// the canvas to the background color, see [`Canvas::set_persistent()`]
+  pub persistent: bool,
+  /// How smoothly the edges of shapes are drawn, see [`RenderQuality`]
+  pub quality: RenderQuality,
+  /// A key that saves the current frame as a PNG file in the working
+  /// directory when pressed, see [`Canvas::save_png()`]
+  pub screenshot_key: Option<KeyCode>,
+  /// A key that starts and stops recording frames to an animated GIF in
+  /// the working directory when pressed, see [`Canvas::start_recording()`].
+  /// Frames are timed from the target frame rate, or the measured frame
+  /// rate without one, so the GIF plays back at about the speed of the app
+  pub record_key: Option<KeyCode>,
+  /// Lets the user resize the window
+  pub resizable: bool,
+  /// How the canvas is fit into the window when the window
+  /// is resized, see [`ScalingMode`]
+  pub scaling: ScalingMode,
+  /// How many pixels wide each canvas pixel is shown in the window,
+  /// which makes the window `scale` times the size of the canvas.
+  /// The canvas is scaled up without smoothing, so it stays crisp for
+  /// pixel art. Must be at least `1`
+  pub scale: u32,
+  /// Starts the window covering the whole screen, see [`Canvas::set_fullscreen()`]
+  pub fullscreen: Option<FullscreenMode>,
+  /// A key that switches the window in and out of fullscreen when
+  /// pressed, such as `Some(KeyCode::F11)`. Uses the mode in
+  /// [`CanvasConfiguration::fullscreen`], or borderless if there is none
+  pub fullscreen_key: Option<KeyCode>,
+  /// How many times per second [`Run::update()`] is called.
+  /// Must be at least `1`
+  pub update_rate: u32,
+  /// The most frames drawn per second, or `None` to draw frames as
+  /// fast as possible, see [`Canvas::set_target_frame_rate()`]
+  pub target_frame_rate: Option<u32>,
+  /// Waits for the screen to refresh before showing each frame,
+  /// which stops tearing, see [`Canvas::set_vsync()`]
+  pub vsync: bool,
+  /// Draws frames continuously. Otherwise frames are only drawn when
+  /// input arrives or the app asks for one, see [`Canvas::set_looping()`]
+  pub looping: bool,
+}
+
+impl Default for CanvasConfiguration {
+  fn default() -> Self {
+    return Self {
+      width: 400,
+      height: 400,
+      background_color: Color::from_rgba(0.0, 0.0, 0.0, 1.0),
+      window_name: String::from("waow!"),
+      persistent: false,
+      quality: RenderQuality::Fast,
+      screenshot_key: None,
+      record_key: None,
+      resizable: false,
+      scaling: ScalingMode::Letterbox,
+      scale: 1,
+      fullscreen: None,
+      fullscreen_key: None,
+      update_rate: 60,
+      target_frame_rate: None,
+      vsync: true,
+      looping: true,
+    };
+  }
 }
 
 /// Defines the behavior for an app that effects the canvas
 ///
 /// The `start()` method is called before the first frame
 /// The `draw()` method is called every frame
+/// The optional `update()` method is called at a fixed rate
+/// The optional `resized()` method is called when the size of the
+/// canvas changes, see [`ScalingMode::Resize`]
+/// The other optional methods are called as soon as input arrives,
+/// even between frames, so short key taps and clicks are never missed
 ///
 /// # Examples
 /// ```
@@ -76,21 +193,127 @@ pub struct CanvasConfiguration {
 /// impl Run for App {
 ///   fn start(&mut self, _canvas: &mut Canvas) {}
 ///   fn draw(&mut self, canvas: &mut Canvas, _input: &Input) {
-///     canvas.draw_square(50, 50, 20, Color::from_rgba(1.0, 0.0, 0.0, 1.0), None)
+///     canvas.draw_square(50.0, 50.0, 20.0, Color::from_rgba(1.0, 0.0, 0.0, 1.0), None)
+///   }
+/// }
+/// ```
+///
+/// ```
+/// use waow::*;
+///
+/// struct App {
+///   clicks: Vec<(f32, f32)>,
+/// }
+///
+/// impl Run for App {
+///   fn start(&mut self, _canvas: &mut Canvas) {}
+///
+///   fn draw(&mut self, canvas: &mut Canvas, _input: &Input) {
+///     for (x, y) in self.clicks.iter() {
+///       canvas.draw_square(*x - 2.0, *y - 2.0, 4.0, Color::from_rgba(1.0, 1.0, 1.0, 1.0), None);
+///     }
+///   }
+///
+///   fn mouse_pressed(&mut self, _canvas: &mut Canvas, button: usize, x: f32, y: f32) {
+///     if button == 0 {
+///       self.clicks.push((x, y));
+///     }
+///   }
+///
+///   fn key_pressed(&mut self, _canvas: &mut Canvas, key: KeyCode) {
+///     if key == KeyCode::Back {
+///       self.clicks.pop();
+///     }
 ///   }
 /// }
 /// ```
 pub trait Run {
   fn start(&mut self, canvas: &mut Canvas);
   fn draw(&mut self, canvas: &mut Canvas, input: &Input);
+
+  /// Called [`CanvasConfiguration::update_rate`] times per second,
+  /// with `delta_time` being the time between updates in seconds
+  ///
+  /// Updates are not tied to frames, so the app runs at the same speed
+  /// no matter how fast frames are drawn. Any number of updates can
+  /// happen before each frame, including none, so presses from
+  /// [`Input::key_pressed()`] should be checked in `draw()`
+  ///
+  /// # Examples
+  /// ```
+  /// use waow::*;
+  ///
+  /// struct App {
+  ///   x: f32,
+  ///   previous_x: f32,
+  /// }
+  ///
+  /// impl Run for App {
+  ///   fn start(&mut self, _canvas: &mut Canvas) {}
+  ///
+  ///   fn update(&mut self, _canvas: &mut Canvas, _input: &Input, delta_time: f32) {
+  ///     // moves 50 pixels per second
+  ///     self.previous_x = self.x;
+  ///     self.x += 50.0 * delta_time;
+  ///   }
+  ///
+  ///   fn draw(&mut self, canvas: &mut Canvas, _input: &Input) {
+  ///     // draw the square between where it was and where it is
+  ///     let t = canvas.get_interpolation();
+  ///     let x = self.previous_x + (self.x - self.previous_x) * t;
+  ///     canvas.draw_square(x, 50.0, 10.0, Color::from_rgba(1.0, 1.0, 1.0, 1.0), None);
+  ///   }
+  /// }
+  /// ```
+  fn update(&mut self, _canvas: &mut Canvas, _input: &Input, _delta_time: f32) {}
+
+  /// Called when the canvas is resized to `width` x `height`
+  fn resized(&mut self, _canvas: &mut Canvas, _width: u32, _height: u32) {}
+
+  /// Called when `key` is pressed, but not when it repeats while held
+  fn key_pressed(&mut self, _canvas: &mut Canvas, _key: KeyCode) {}
+
+  /// Called when `key` is released
+  fn key_released(&mut self, _canvas: &mut Canvas, _key: KeyCode) {}
+
+  /// Called when a mouse button is pressed with the mouse at (`x`, `y`)
+  ///
+  /// The buttons are numbered the same as [`Input::mouse_pressed()`],
+  /// with `0` for left, `1` for right and `2` for middle
+  fn mouse_pressed(&mut self, _canvas: &mut Canvas, _button: usize, _x: f32, _y: f32) {}
+
+  /// Called when a mouse button is released with the mouse at (`x`, `y`),
+  /// see [`Run::mouse_pressed()`]
+  fn mouse_released(&mut self, _canvas: &mut Canvas, _button: usize, _x: f32, _y: f32) {}
+
+  /// Called when the mouse moves to (`x`, `y`) on the canvas
+  fn mouse_moved(&mut self, _canvas: &mut Canvas, _x: f32, _y: f32) {}
+
+  /// Called when the mouse wheel is scrolled by `delta` lines,
+  /// which is positive when scrolling up
+  fn mouse_wheel(&mut self, _canvas: &mut Canvas, _delta: f32) {}
+
+  /// Called when the window gains or loses focus
+  fn focus_changed(&mut self, _canvas: &mut Canvas, _focused: bool) {}
+
+  /// Called once when the window is closing, after the last frame
+  fn exit(&mut self, _canvas: &mut Canvas) {}
 }
 
 /// Creates a canvas to draw on
 ///
 /// Creates a new window with the given [`CanvasConfiguration`]
 /// and runs the app with the canvas
+///
+/// The mouse position given by [`Input::mouse()`] is in canvas pixels,
+/// no matter how the canvas is scaled to fit the window
+///
+/// # Panics
+/// panics if [`CanvasConfiguration::scale`],
+/// [`CanvasConfiguration::update_rate`] or
+/// [`CanvasConfiguration::target_frame_rate`] is `0`
 /// # Examples
-/// ```
+/// ```no_run
 /// use waow::*;
 ///
 /// fn main() {
@@ -102,6 +325,7 @@ pub trait Run {
 ///       height: 400,
 ///       background_color: Color::from_rgba(0.0, 0.0, 0.0, 1.0),
 ///       window_name: String::from("waow!"),
+///       ..Default::default()
 ///     },
 ///   );
 /// }
@@ -115,7 +339,7 @@ pub trait Run {
 ///       if input.key_held(KeyCode::D) {
 ///         color = Color::from_rgba(0.0, 1.0, 0.0, 1.0);
 ///       }
-///       canvas.draw_square(mouse_pos.0 as i16, mouse_pos.1 as i16, 20, color, None);
+///       canvas.draw_square(mouse_pos.0, mouse_pos.1, 20.0, color, None);
 ///     }
 ///   }
 /// }
@@ -126,50 +350,368 @@ pub fn create(mut app: impl Run + 'static, config: CanvasConfiguration) {
   let mut input = Input::new();
 
   let mut canvas = Canvas::new(&config);
+  let screenshot_key = config.screenshot_key;
+  let record_key = config.record_key;
+  let fullscreen_key = config.fullscreen_key;
+  let fullscreen_mode = config.fullscreen.unwrap_or_default();
+  let mut fullscreen = config.fullscreen;
+  let mut vsync = config.vsync;
+
+  assert!(config.scale > 0, "scale must be greater than 0");
+  let scale = config.scale;
+  let mut timestep = FixedTimestep::new(config.update_rate);
 
   let (width, height) = (config.width, config.height);
   let window = {
-    let size = LogicalSize::new(width as f64, height as f64);
-    WindowBuilder::new()
+    let size = LogicalSize::new((width * scale) as f64, (height * scale) as f64);
+    let mut builder = WindowBuilder::new()
       .with_title(config.window_name)
       .with_inner_size(size)
-      .with_resizable(false)
-      .with_min_inner_size(size)
-      .build(&event_loop)
-      .unwrap()
+      .with_resizable(config.resizable)
+      .with_fullscreen(
+        config
+          .fullscreen
+          .map(|mode| mode.to_fullscreen(event_loop.primary_monitor())),
+      );
+    // a letterboxed canvas can't be scaled down to fit a smaller window
+    if config.scaling == ScalingMode::Letterbox {
+      builder = builder.with_min_inner_size(size);
+    }
+    builder.build(&event_loop).unwrap()
   };
 
-  let mut pixels = {
-    let window_size = window.inner_size();
-    let surface_texture = SurfaceTexture::new(window_size.width, window_size.height, &window);
-    Pixels::new(width, height, surface_texture).unwrap()
-  };
+  let mut viewport = Viewport::new(
+    config.scaling,
+    window.inner_size().into(),
+    (width, height),
+    window.scale_factor() * scale as f64,
+  );
+  // the canvas is drawn here first when it has to be stretched into the window
+  let mut canvas_frame = Vec::<u8>::new();
 
+  // only `None` while the pixels are being rebuilt
+  let mut pixels = Some(build_pixels(&window, &viewport, vsync).unwrap());
+
+  let (canvas_width, canvas_height) = viewport.get_canvas_size();
+  canvas.resize(canvas_width, canvas_height);
   app.start(&mut canvas);
+  // the first frame is drawn even if the app only draws on demand
+  canvas.redraw();
 
-  event_loop.run(move |event, _, control_flow| {
-    if let Event::RedrawRequested(_) = event {
-      canvas.draw_to_buffer(pixels.frame_mut());
+  // window events since the last frame, which the input is updated with
+  // when the next frame is drawn, so no presses are missed between frames
+  let mut events = Vec::<WindowEvent<'static>>::new();
+  let mut last_step = Instant::now();
+  let mut next_frame = Instant::now();
+  let mut frame_ready = false;
+  let mut needs_resize = false;
+  // kept for the callbacks, see `dispatch_event()`
+  let mut held_keys = HashSet::<KeyCode>::new();
+  let mut mouse_position = (0.0, 0.0);
 
-      if let Err(err) = pixels.render() {
-        log_error("pixels.render", err);
-        *control_flow = ControlFlow::Exit;
-        return;
-      }
+  event_loop.run(move |mut event, _, control_flow| {
+    // the app sees the mouse position in canvas pixels
+    if let Event::WindowEvent {
+      event: WindowEvent::CursorMoved { position, .. },
+      ..
+    } = &mut event
+    {
+      let (x, y) = viewport.window_to_canvas(position.x, position.y);
+      *position = PhysicalPosition::new(x, y);
     }
 
-    if input.update(&event) {
-      if input.close_requested() {
-        *control_flow = ControlFlow::Exit;
-        return;
+    match event {
+      Event::WindowEvent { event, .. } => {
+        if let WindowEvent::CloseRequested = event {
+          *control_flow = ControlFlow::Exit;
+          return;
+        }
+
+        // scale factor changes can't be queued for the input helper,
+        // see `WindowEvent::to_static()`, so they're tracked here
+        if let WindowEvent::Resized(_) | WindowEvent::ScaleFactorChanged { .. } = event {
+          needs_resize = true;
+        }
+
+        dispatch_event(&mut app, &mut canvas, &event, &mut held_keys, &mut mouse_position);
+        if let Some(event) = event.to_static() {
+          events.push(event);
+        }
+      }
+      Event::RedrawRequested(_) => {
+        let Some(pixels) = &mut pixels else {
+          return;
+        };
+
+        // the window can ask to be redrawn between frames,
+        // in which case the last frame is shown again
+        if frame_ready {
+          if viewport.get_buffer_size() == canvas.get_size() {
+            canvas.draw_to_buffer(pixels.frame_mut());
+          } else {
+            let (canvas_width, canvas_height) = canvas.get_size();
+            canvas_frame.resize((canvas_width * canvas_height * 4) as usize, 0);
+            canvas.draw_to_buffer(&mut canvas_frame);
+            scaling::stretch(
+              &canvas_frame,
+              canvas.get_size(),
+              pixels.frame_mut(),
+              viewport.get_buffer_size(),
+            );
+          }
+          frame_ready = false;
+        }
+
+        if let Err(err) = pixels.render() {
+          log_error("pixels.render", err);
+          *control_flow = ControlFlow::Exit;
+        }
       }
+      Event::MainEventsCleared => {
+        let now = Instant::now();
+        let wants_frame = canvas.is_looping()
+          || canvas.is_redraw_requested()
+          || needs_resize
+          || !events.is_empty();
+
+        if wants_frame && now >= next_frame {
+          input.step_with_window_events(&events);
+          events.clear();
+          canvas.clear_redraw_request();
+
+          if needs_resize {
+            needs_resize = false;
+            if let Some(pixels) = &mut pixels {
+              if let Err(err) = resize_window(&window, pixels, &mut viewport, scale) {
+                log_error("pixels.resize_surface", err);
+                *control_flow = ControlFlow::Exit;
+                return;
+              }
+            }
+
+            let (canvas_width, canvas_height) = viewport.get_canvas_size();
+            if canvas.get_size() != (canvas_width, canvas_height) {
+              canvas.resize(canvas_width, canvas_height);
+              app.resized(&mut canvas, canvas_width, canvas_height);
+            }
+          }
+
+          if let Some(key) = screenshot_key {
+            if input.key_pressed(key) {
+              save_screenshot(&canvas);
+            }
+          }
+
+          if let Some(key) = record_key {
+            if input.key_pressed(key) {
+              toggle_recording(&mut canvas);
+            }
+          }
+
+          if let Some(key) = fullscreen_key {
+            if input.key_pressed(key) {
+              match canvas.get_fullscreen() {
+                Some(_) => canvas.set_fullscreen(None),
+                None => canvas.set_fullscreen(Some(fullscreen_mode)),
+              }
+            }
+          }
+
+          canvas.tick(now - last_step);
+          let updates = timestep.advance((now - last_step).min(MAX_FRAME_TIME));
+          last_step = now;
+          for _ in 0..updates {
+            app.update(&mut canvas, &input, timestep.get_delta_time());
+          }
+          canvas.set_interpolation(timestep.get_interpolation());
+
+          app.draw(&mut canvas, &input);
+          frame_ready = true;
+
+          // the window is resized afterwards, which fits the canvas to the screen
+          if canvas.get_fullscreen() != fullscreen {
+            fullscreen = canvas.get_fullscreen();
+            window.set_fullscreen(fullscreen.map(|mode| mode.to_fullscreen(window.current_monitor())));
+          }
 
-      app.draw(&mut canvas, &input);
-      window.request_redraw();
+          if canvas.get_vsync() != vsync {
+            vsync = canvas.get_vsync();
+            // the old surface has to be dropped before a new one can be made for the window
+            pixels = None;
+            match build_pixels(&window, &viewport, vsync) {
+              Ok(new_pixels) => pixels = Some(new_pixels),
+              Err(err) => {
+                log_error("pixels.build", err);
+                *control_flow = ControlFlow::Exit;
+                return;
+              }
+            }
+          }
+          window.request_redraw();
+
+          next_frame = match canvas.get_target_frame_rate() {
+            Some(frame_rate) => (next_frame + Duration::from_secs(1) / frame_rate).max(now),
+            None => now,
+          };
+        }
+
+        // sleep until the next frame is due, or until an event arrives
+        // when frames are only drawn on demand. A frame that was just drawn
+        // wakes the loop once more, which checks again with the new state
+        *control_flow = if !wants_frame {
+          ControlFlow::Wait
+        } else if next_frame > Instant::now() {
+          ControlFlow::WaitUntil(next_frame)
+        } else {
+          ControlFlow::Poll
+        };
+      }
+      Event::LoopDestroyed => app.exit(&mut canvas),
+      _ => {}
     }
   });
 }
 
+/// Creates the buffer that the canvas is drawn into to show it in the window
+fn build_pixels(window: &Window, viewport: &Viewport, vsync: bool) -> Result<Pixels, pixels::Error> {
+  let (window_width, window_height) = viewport.get_window_size();
+  let surface_texture = SurfaceTexture::new(window_width, window_height, window);
+  let (buffer_width, buffer_height) = viewport.get_buffer_size();
+  return PixelsBuilder::new(buffer_width, buffer_height, surface_texture)
+    .enable_vsync(vsync)
+    .build();
+}
+
+/// Calls the app's callback for `event`, if it has one
+///
+/// `held_keys` is used to skip repeated presses of held keys, and
+/// `mouse_position` is the last position the mouse moved to
+fn dispatch_event(
+  app: &mut impl Run,
+  canvas: &mut Canvas,
+  event: &WindowEvent,
+  held_keys: &mut HashSet<KeyCode>,
+  mouse_position: &mut (f32, f32),
+) {
+  match event {
+    WindowEvent::KeyboardInput {
+      input: KeyboardInput {
+        virtual_keycode: Some(key),
+        state,
+        ..
+      },
+      ..
+    } => match state {
+      ElementState::Pressed => {
+        if held_keys.insert(*key) {
+          app.key_pressed(canvas, *key);
+        }
+      }
+      ElementState::Released => {
+        held_keys.remove(key);
+        app.key_released(canvas, *key);
+      }
+    },
+    WindowEvent::MouseInput { state, button, .. } => {
+      let button = match button {
+        MouseButton::Left => 0,
+        MouseButton::Right => 1,
+        MouseButton::Middle => 2,
+        MouseButton::Other(button) => *button as usize,
+      };
+      let (x, y) = *mouse_position;
+      match state {
+        ElementState::Pressed => app.mouse_pressed(canvas, button, x, y),
+        ElementState::Released => app.mouse_released(canvas, button, x, y),
+      }
+    }
+    WindowEvent::CursorMoved { position, .. } => {
+      *mouse_position = (position.x as f32, position.y as f32);
+      app.mouse_moved(canvas, mouse_position.0, mouse_position.1);
+    }
+    WindowEvent::MouseWheel { delta, .. } => {
+      let delta = match delta {
+        MouseScrollDelta::LineDelta(_, y) => *y,
+        MouseScrollDelta::PixelDelta(position) => (position.y / PIXELS_PER_SCROLL_LINE) as f32,
+      };
+      app.mouse_wheel(canvas, delta);
+    }
+    WindowEvent::Focused(focused) => {
+      // keys released while the window isn't focused are never reported
+      if !focused {
+        held_keys.clear();
+      }
+      app.focus_changed(canvas, *focused);
+    }
+    _ => {}
+  }
+}
+
+/// Fits the canvas into the new size of the window, with each canvas
+/// pixel `scale` logical pixels wide
+fn resize_window(
+  window: &Window,
+  pixels: &mut Pixels,
+  viewport: &mut Viewport,
+  scale: u32,
+) -> Result<(), TextureError> {
+  let size = window.inner_size();
+  viewport.resize_window(size.width, size.height, window.scale_factor() * scale as f64);
+
+  let (window_width, window_height) = viewport.get_window_size();
+  let (buffer_width, buffer_height) = viewport.get_buffer_size();
+  pixels.resize_surface(window_width, window_height)?;
+  pixels.resize_buffer(buffer_width, buffer_height)?;
+  return Ok(());
+}
+
+fn save_screenshot(canvas: &Canvas) {
+  let path = format!("screenshot-{}.png", timestamp());
+
+  match canvas.save_png(&path) {
+    Ok(()) => info!("saved screenshot to {path}"),
+    Err(err) => log_error("canvas.save_png", err),
+  }
+}
+
+fn toggle_recording(canvas: &mut Canvas) {
+  if canvas.is_recording() {
+    match canvas.stop_recording() {
+      Ok(()) => info!("stopped recording"),
+      Err(err) => log_error("canvas.stop_recording", err),
+    }
+    return;
+  }
+
+  let path = format!("recording-{}.gif", timestamp());
+  match canvas.start_recording(&path, recording_frame_delay(canvas)) {
+    Ok(()) => info!("recording to {path}"),
+    Err(err) => log_error("canvas.start_recording", err),
+  }
+}
+
+/// Gets how long each recorded frame is shown, from the target frame rate
+/// if there is one, otherwise from the measured frame rate
+fn recording_frame_delay(canvas: &Canvas) -> Duration {
+  if let Some(frame_rate) = canvas.get_target_frame_rate() {
+    return Duration::from_secs(1) / frame_rate;
+  }
+
+  let fps = canvas.get_fps();
+  if fps > 0.0 {
+    return Duration::from_secs_f32(1.0 / fps);
+  }
+  return RECORDING_FRAME_DELAY;
+}
+
+/// The number of milliseconds since the unix epoch, used to name saved files
+fn timestamp() -> u128 {
+  return SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .unwrap_or_default()
+    .as_millis();
+}
+
 fn log_error<E: std::error::Error + 'static>(method_name: &str, err: E) {
   error!("{method_name}() failed: {err}");
   for source in err.sources().skip(1) {
@@ -0,0 +1,84 @@
+//! Records the frames drawn by a canvas into an animated GIF
+//!
+//! Each frame is reduced to a palette of at most 256 colors,
+//! see [`Canvas::start_recording()`](crate::Canvas::start_recording)
+
+use std::{
+  fs::File,
+  io::{self, BufWriter, Write},
+  path::Path,
+  time::Duration,
+};
+
+use gif::{Encoder, Frame, Repeat};
+
+/// How much quality is traded for speed when reducing a frame to 256 colors,
+/// from `1` (best quality) to `30` (fastest)
+const QUANTIZATION_SPEED: i32 = 10;
+
+/// The unit that gif frame delays are measured in, a hundredth of a second
+const DELAY_UNIT: Duration = Duration::from_millis(10);
+
+pub(crate) struct Recording {
+  encoder: Encoder<BufWriter<File>>,
+  width: u16,
+  height: u16,
+  frame_delay: Duration,
+  pending_delay: Duration,
+}
+
+impl Recording {
+  /// Creates the GIF file at `path` that frames of
+  /// `width` x `height` pixels are added to
+  pub(crate) fn new(
+    path: impl AsRef<Path>,
+    width: u32,
+    height: u32,
+    frame_delay: Duration,
+  ) -> io::Result<Self> {
+    let (width, height) = (
+      u16::try_from(width).map_err(io::Error::other)?,
+      u16::try_from(height).map_err(io::Error::other)?,
+    );
+
+    let file = BufWriter::new(File::create(path)?);
+    let mut encoder = Encoder::new(file, width, height, &[]).map_err(io::Error::other)?;
+    encoder
+      .set_repeat(Repeat::Infinite)
+      .map_err(io::Error::other)?;
+
+    return Ok(Self {
+      encoder,
+      width,
+      height,
+      frame_delay,
+      // starting half a unit in rounds each delay to the nearest unit
+      pending_delay: DELAY_UNIT / 2,
+    });
+  }
+
+  /// Adds a frame of RGBA bytes to the end of the GIF
+  pub(crate) fn add_frame(&mut self, rgba: &mut [u8]) -> io::Result<()> {
+    let mut frame = Frame::from_rgba_speed(self.width, self.height, rgba, QUANTIZATION_SPEED);
+    frame.delay = self.next_delay();
+    return self.encoder.write_frame(&frame).map_err(io::Error::other);
+  }
+
+  /// Gets the delay of the next frame in whole units, carrying the time
+  /// left over to later frames so the delays add up to `frame_delay` each
+  ///
+  /// At 60 fps, frames are shown for 2, 1, 2, 2, 1, 2... hundredths of a second.
+  /// Every frame is shown for at least one unit
+  fn next_delay(&mut self) -> u16 {
+    self.pending_delay += self.frame_delay;
+    let units = self.pending_delay.as_nanos() / DELAY_UNIT.as_nanos();
+    let units = units.clamp(1, u16::MAX as u128);
+    self.pending_delay = self.pending_delay.saturating_sub(DELAY_UNIT * units as u32);
+    return units as u16;
+  }
+
+  /// Finishes writing the GIF file
+  pub(crate) fn finish(self) -> io::Result<()> {
+    return self.encoder.into_inner()?.flush();
+  }
+}
@@ -0,0 +1,217 @@
+//! Fits the canvas into a window that can be a different size,
+//! see [`ScalingMode`]
+
+/// How the canvas is fit into the window when their sizes are different,
+/// such as when the window is resized
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ScalingMode {
+  /// Keeps the canvas size, scaling it up by the largest whole number
+  /// that fits in the window, with black bars around it
+  #[default]
+  Letterbox,
+  /// Keeps the canvas size, stretching it to fill the whole window
+  Stretch,
+  /// Resizes the canvas to match the window,
+  /// see [`Run::resized()`](crate::Run::resized)
+  Resize,
+}
+
+/// Tracks the sizes of the window and the canvas shown in it
+pub(crate) struct Viewport {
+  mode: ScalingMode,
+  window_width: u32,
+  window_height: u32,
+  canvas_width: u32,
+  canvas_height: u32,
+}
+
+impl Viewport {
+  /// `window_width` and `window_height` are in physical pixels, and each
+  /// canvas pixel is meant to be `pixel_size` physical pixels wide
+  pub(crate) fn new(
+    mode: ScalingMode,
+    (window_width, window_height): (u32, u32),
+    (canvas_width, canvas_height): (u32, u32),
+    pixel_size: f64,
+  ) -> Self {
+    let mut viewport = Self {
+      mode,
+      window_width,
+      window_height,
+      canvas_width,
+      canvas_height,
+    };
+    viewport.resize_window(window_width, window_height, pixel_size);
+    return viewport;
+  }
+
+  /// Updates the size of the window, resizing the canvas too
+  /// if the canvas follows the window
+  pub(crate) fn resize_window(&mut self, width: u32, height: u32, pixel_size: f64) {
+    self.window_width = width.max(1);
+    self.window_height = height.max(1);
+
+    if self.mode == ScalingMode::Resize {
+      self.canvas_width = ((self.window_width as f64 / pixel_size).round() as u32).max(1);
+      self.canvas_height = ((self.window_height as f64 / pixel_size).round() as u32).max(1);
+    }
+  }
+
+  /// Gets the (width, height) of the window in physical pixels
+  pub(crate) fn get_window_size(&self) -> (u32, u32) {
+    return (self.window_width, self.window_height);
+  }
+
+  /// Gets the (width, height) of the canvas
+  pub(crate) fn get_canvas_size(&self) -> (u32, u32) {
+    return (self.canvas_width, self.canvas_height);
+  }
+
+  /// Gets the (width, height) of the buffer shown in the window
+  ///
+  /// The buffer is the size of the canvas when it is scaled by a whole
+  /// number, otherwise the canvas is stretched into a buffer the size
+  /// of the window, see [`stretch()`]
+  pub(crate) fn get_buffer_size(&self) -> (u32, u32) {
+    return match self.mode {
+      ScalingMode::Letterbox => self.get_canvas_size(),
+      ScalingMode::Stretch | ScalingMode::Resize if self.scales_evenly() => {
+        self.get_canvas_size()
+      }
+      ScalingMode::Stretch | ScalingMode::Resize => self.get_window_size(),
+    };
+  }
+
+  /// Converts a position in the window in physical pixels
+  /// to a position on the canvas
+  pub(crate) fn window_to_canvas(&self, x: f64, y: f64) -> (f64, f64) {
+    let (window_width, window_height) = (self.window_width as f64, self.window_height as f64);
+    let (canvas_width, canvas_height) = (self.canvas_width as f64, self.canvas_height as f64);
+
+    return match self.mode {
+      ScalingMode::Letterbox => {
+        let scale = self.get_letterbox_scale();
+        let offset_x = ((window_width - canvas_width * scale) / 2.0).floor();
+        let offset_y = ((window_height - canvas_height * scale) / 2.0).floor();
+        ((x - offset_x) / scale, (y - offset_y) / scale)
+      }
+      ScalingMode::Stretch | ScalingMode::Resize => (
+        x * canvas_width / window_width,
+        y * canvas_height / window_height,
+      ),
+    };
+  }
+
+  /// The largest whole number the canvas can be scaled by
+  /// while still fitting in the window
+  fn get_letterbox_scale(&self) -> f64 {
+    let scale_x = self.window_width as f64 / self.canvas_width as f64;
+    let scale_y = self.window_height as f64 / self.canvas_height as f64;
+    return scale_x.min(scale_y).floor().max(1.0);
+  }
+
+  /// Checks whether the canvas fills the window when scaled by a whole number
+  fn scales_evenly(&self) -> bool {
+    return self.window_width.is_multiple_of(self.canvas_width)
+      && self.window_height.is_multiple_of(self.canvas_height)
+      && self.window_width / self.canvas_width == self.window_height / self.canvas_height;
+  }
+}
+
+/// Copies the RGBA bytes in `source` into `destination`, scaling it
+/// to fit using the nearest pixel
+pub(crate) fn stretch(
+  source: &[u8],
+  (source_width, source_height): (u32, u32),
+  destination: &mut [u8],
+  (destination_width, destination_height): (u32, u32),
+) {
+  let (source_width, source_height) = (source_width as usize, source_height as usize);
+  let destination_width = destination_width as usize;
+  let destination_height = destination_height as usize;
+
+  let columns: Vec<usize> = (0..destination_width)
+    .map(|x| (x * source_width / destination_width) * 4)
+    .collect();
+
+  for (y, row) in destination.chunks_exact_mut(destination_width * 4).enumerate() {
+    let source_y = y * source_height / destination_height;
+    let source_row = &source[source_y * source_width * 4..(source_y + 1) * source_width * 4];
+    for (pixel, &column) in row.chunks_exact_mut(4).zip(columns.iter()) {
+      pixel.copy_from_slice(&source_row[column..column + 4]);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn letterbox_centers_canvas_at_whole_scale() {
+    let viewport = Viewport::new(ScalingMode::Letterbox, (250, 130), (100, 50), 1.0);
+
+    // scaled by 2, leaving 50 x 30 pixels of bars split between both sides
+    assert_eq!(viewport.get_letterbox_scale(), 2.0);
+    assert_eq!(viewport.get_buffer_size(), (100, 50));
+    assert_eq!(viewport.window_to_canvas(25.0, 15.0), (0.0, 0.0));
+    assert_eq!(viewport.window_to_canvas(225.0, 115.0), (100.0, 50.0));
+    assert_eq!(viewport.window_to_canvas(0.0, 0.0), (-12.5, -7.5));
+  }
+
+  #[test]
+  fn letterbox_rounds_odd_offsets_down() {
+    let viewport = Viewport::new(ScalingMode::Letterbox, (101, 51), (50, 25), 1.0);
+    assert_eq!(viewport.window_to_canvas(0.0, 0.0), (0.0, 0.0));
+    assert_eq!(viewport.window_to_canvas(100.0, 50.0), (50.0, 25.0));
+  }
+
+  #[test]
+  fn stretch_maps_window_at_fractional_ratio() {
+    let viewport = Viewport::new(ScalingMode::Stretch, (150, 75), (100, 50), 1.0);
+
+    assert!(!viewport.scales_evenly());
+    assert_eq!(viewport.get_buffer_size(), (150, 75));
+    assert_eq!(viewport.window_to_canvas(75.0, 30.0), (50.0, 20.0));
+    assert_eq!(viewport.window_to_canvas(150.0, 75.0), (100.0, 50.0));
+  }
+
+  #[test]
+  fn stretch_uses_canvas_buffer_at_whole_ratio() {
+    let viewport = Viewport::new(ScalingMode::Stretch, (200, 100), (100, 50), 1.0);
+
+    assert!(viewport.scales_evenly());
+    assert_eq!(viewport.get_buffer_size(), (100, 50));
+
+    // same multiple, but not in both directions
+    let viewport = Viewport::new(ScalingMode::Stretch, (200, 150), (100, 50), 1.0);
+    assert!(!viewport.scales_evenly());
+    assert_eq!(viewport.get_buffer_size(), (200, 150));
+  }
+
+  #[test]
+  fn resize_follows_window_at_fractional_scale_factor() {
+    let mut viewport = Viewport::new(ScalingMode::Resize, (300, 200), (10, 10), 1.5);
+
+    assert_eq!(viewport.get_canvas_size(), (200, 133));
+    assert_eq!(viewport.get_buffer_size(), (300, 200));
+    assert_eq!(viewport.window_to_canvas(150.0, 100.0), (100.0, 66.5));
+
+    viewport.resize_window(0, 0, 1.5);
+    assert_eq!(viewport.get_window_size(), (1, 1));
+    assert_eq!(viewport.get_canvas_size(), (1, 1));
+  }
+
+  #[test]
+  fn stretch_copies_nearest_pixels() {
+    let red = [255, 0, 0, 255];
+    let blue = [0, 0, 255, 255];
+    let source = [red, blue].concat();
+
+    let mut destination = vec![0; 3 * 2 * 4];
+    stretch(&source, (2, 1), &mut destination, (3, 2));
+
+    let row = [red, red, blue].concat();
+    assert_eq!(destination, [row.clone(), row].concat());
+  }
+}
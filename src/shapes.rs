@@ -6,10 +6,14 @@
 
 use crate::Color;
 
+mod bounds;
 mod circle;
 mod image;
 mod rectangle;
 
+/// The rectangle of pixels that a shape covers
+pub use bounds::Bounds;
+pub(crate) use bounds::clamp_coordinate;
 /// A circle with a fill and border
 pub use circle::Circle;
 /// An array of pixels that can be drawn to the screen
@@ -24,6 +28,23 @@ pub use rectangle::Rectangle;
 ///
 /// returns `None` if the position is not within the object
 ///
+/// the returned color is composited on top of whatever has already
+/// been drawn at that pixel, so colors with an alpha below `1.0`
+/// are see-through
+///
+/// the optional `get_bounds()` and `get_row_span()` methods tell the
+/// canvas which pixels the object can cover, so that `get_color()` is
+/// only called for those pixels. By default the whole canvas is checked
+///
+/// the optional `get_color_antialiased()` method is used instead of
+/// `get_color()` when the canvas is drawing with
+/// [`RenderQuality::Antialiased`](crate::RenderQuality::Antialiased),
+/// and the optional `sample()` method is used when drawing with
+/// [`RenderQuality::Supersampled`](crate::RenderQuality::Supersampled)
+///
+/// objects are drawn from multiple threads at once, so they
+/// must be `Send` and `Sync`
+///
 /// # Examples
 /// ```
 /// use waow::*;
@@ -34,7 +55,7 @@ pub use rectangle::Rectangle;
 /// }
 ///
 /// impl shapes::Drawable for DrawEvenX {
-///   fn get_color(&self, x: i16, y: i16) -> Option<Color> {
+///   fn get_color(&self, x: i32, y: i32) -> Option<Color> {
 ///     if x % 2 == 0 {
 ///       return Some(self.color);
 ///     } else {
@@ -43,6 +64,91 @@ pub use rectangle::Rectangle;
 ///   }
 /// }
 /// ```
-pub trait Drawable {
-  fn get_color(&self, x: i16, y: i16) -> Option<Color>;
+///
+/// ```
+/// use waow::*;
+///
+/// #[derive(Clone)]
+/// struct Dot {
+///   x: i32,
+///   y: i32,
+///   color: Color,
+/// }
+///
+/// impl shapes::Drawable for Dot {
+///   fn get_color(&self, x: i32, y: i32) -> Option<Color> {
+///     if x == self.x && y == self.y {
+///       return Some(self.color);
+///     } else {
+///       return None;
+///     }
+///   }
+///
+///   fn get_bounds(&self) -> Option<shapes::Bounds> {
+///     return Some(shapes::Bounds::new(self.x, self.y, 1, 1));
+///   }
+/// }
+/// ```
+pub trait Drawable: Send + Sync {
+  fn get_color(&self, x: i32, y: i32) -> Option<Color>;
+
+  /// Gets the color of the object at a pixel, with the alpha
+  /// reduced by how little of the pixel the object covers
+  ///
+  /// Defaults to [`Drawable::get_color()`], which does not smooth the edges
+  fn get_color_antialiased(&self, x: i32, y: i32) -> Option<Color> {
+    return self.get_color(x, y);
+  }
+
+  /// Gets the color of the object at a point that can be between pixels
+  ///
+  /// The pixel at (`x`, `y`) covers the points from `x` to `x + 1.0`
+  /// and `y` to `y + 1.0`. Defaults to [`Drawable::get_color()`]
+  /// of the pixel that the point is in
+  ///
+  /// # Examples
+  /// ```
+  /// use waow::*;
+  ///
+  /// #[derive(Clone)]
+  /// struct Ripples {}
+  ///
+  /// impl shapes::Drawable for Ripples {
+  ///   fn get_color(&self, x: i32, y: i32) -> Option<Color> {
+  ///     return self.sample(x as f32 + 0.5, y as f32 + 0.5);
+  ///   }
+  ///
+  ///   fn sample(&self, x: f32, y: f32) -> Option<Color> {
+  ///     let dist = (x * x + y * y).sqrt();
+  ///     if (dist / 4.0).sin() > 0.0 {
+  ///       return Some(Color::from_rgba(1.0, 1.0, 1.0, 1.0));
+  ///     } else {
+  ///       return None;
+  ///     }
+  ///   }
+  /// }
+  /// ```
+  fn sample(&self, x: f32, y: f32) -> Option<Color> {
+    return self.get_color(x.floor() as i32, y.floor() as i32);
+  }
+
+  /// Gets the rectangle of pixels that the object can draw to
+  ///
+  /// returns `None` if the object can draw anywhere on the canvas
+  fn get_bounds(&self) -> Option<Bounds> {
+    return None;
+  }
+
+  /// Gets the range of x coordinates from `start` to `end` (exclusive)
+  /// that the object can draw to in row `y`
+  ///
+  /// returns `None` if the object does not draw anything in the row.
+  /// Defaults to the full width of [`Drawable::get_bounds()`]
+  fn get_row_span(&self, y: i32) -> Option<(i32, i32)> {
+    return match self.get_bounds() {
+      Some(bounds) if y >= bounds.y && y < bounds.bottom() => Some((bounds.x, bounds.right())),
+      Some(_) => None,
+      None => Some((i32::MIN, i32::MAX)),
+    };
+  }
 }
@@ -0,0 +1,124 @@
+//! Represents the rectangle of pixels that a shape covers
+//!
+//! Used by the canvas to skip pixels that a shape can't draw to
+//!
+//! # Examples
+//! ```
+//! use waow::*;
+//!
+//! let bounds = shapes::Bounds::new(10, 10, 30, 20);
+//!
+//! assert!(bounds.contains(15, 25));
+//! assert!(!bounds.contains(40, 10));
+//! ```
+
+/// The furthest a coordinate is allowed from `0` when it is rounded to a
+/// pixel, so that the width and height of any bounds fit in an `i32`
+const MAX_COORDINATE: f32 = 1_000_000_000.0;
+
+/// Limits `value` to the coordinates that can be rounded to a pixel
+/// without overflowing, see [`Bounds::from_edges()`]
+pub(crate) fn clamp_coordinate(value: f32) -> f32 {
+  return value.clamp(-MAX_COORDINATE, MAX_COORDINATE);
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Bounds {
+  pub x: i32,
+  pub y: i32,
+  pub width: i32,
+  pub height: i32,
+}
+
+impl Bounds {
+  /// creates new bounds
+  ///
+  /// Creates new bounds with the given `x` and `y` as its top left corner,
+  /// with a size of `width` x `height`
+  ///
+  /// # Examples
+  /// ```
+  /// use waow::*;
+  ///
+  /// let bounds = shapes::Bounds::new(10, 10, 30, 20);
+  /// ```
+  pub fn new(x: i32, y: i32, width: i32, height: i32) -> Self {
+    return Self {
+      x,
+      y,
+      width,
+      height,
+    };
+  }
+
+  /// Creates the smallest bounds that cover every point from
+  /// (`left`, `top`) to (`right`, `bottom`)
+  ///
+  /// Coordinates far outside of any canvas are clamped,
+  /// so the bounds of huge shapes don't overflow
+  ///
+  /// # Examples
+  /// ```
+  /// use waow::*;
+  ///
+  /// let bounds = shapes::Bounds::from_edges(10.5, 10.0, 39.5, 30.0);
+  /// assert_eq!(bounds, shapes::Bounds::new(10, 10, 30, 20));
+  ///
+  /// let huge = shapes::Bounds::from_edges(-3e9, 0.0, 3e9, 10.0);
+  /// assert!(huge.contains(0, 5));
+  /// ```
+  pub fn from_edges(left: f32, top: f32, right: f32, bottom: f32) -> Self {
+    let (x, y) = (
+      clamp_coordinate(left).floor() as i32,
+      clamp_coordinate(top).floor() as i32,
+    );
+    return Self::new(
+      x,
+      y,
+      clamp_coordinate(right).ceil() as i32 - x,
+      clamp_coordinate(bottom).ceil() as i32 - y,
+    );
+  }
+
+  /// The x coordinate one past the right edge of the bounds
+  pub fn right(&self) -> i32 {
+    return self.x.saturating_add(self.width);
+  }
+
+  /// The y coordinate one past the bottom edge of the bounds
+  pub fn bottom(&self) -> i32 {
+    return self.y.saturating_add(self.height);
+  }
+
+  /// Checks whether the pixel at (`x`, `y`) is within the bounds
+  pub fn contains(&self, x: i32, y: i32) -> bool {
+    return x >= self.x && x < self.right() && y >= self.y && y < self.bottom();
+  }
+
+  /// Gets the area covered by both bounds
+  ///
+  /// returns `None` if the bounds do not overlap
+  ///
+  /// # Examples
+  /// ```
+  /// use waow::*;
+  ///
+  /// let a = shapes::Bounds::new(0, 0, 20, 20);
+  /// let b = shapes::Bounds::new(10, 5, 20, 20);
+  ///
+  /// assert_eq!(a.intersect(&b), Some(shapes::Bounds::new(10, 5, 10, 15)));
+  /// assert_eq!(a.intersect(&shapes::Bounds::new(50, 50, 5, 5)), None);
+  /// ```
+  pub fn intersect(&self, other: &Bounds) -> Option<Bounds> {
+    let x = self.x.max(other.x);
+    let y = self.y.max(other.y);
+    let right = self.right().min(other.right());
+    let bottom = self.bottom().min(other.bottom());
+
+    if right <= x || bottom <= y {
+      return None;
+    }
+
+    return Some(Self::new(x, y, right - x, bottom - y));
+  }
+}
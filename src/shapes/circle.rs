@@ -8,28 +8,28 @@
 //! impl Run for App {
 //!   fn start(&mut self, _canvas: &mut Canvas) {}
 //!   fn draw(&mut self, canvas: &mut Canvas, _input: &Input) {
-//!     let circle = shapes::Circle::new(50, 50, 30)
+//!     let circle = shapes::Circle::new(50.0, 50.0, 30.0)
 //!       .with_fill(Color::from_rgba(1.0, 0.0, 0.0, 1.0))
-//!       .with_border(Color::from_rgba(0.0, 0.0, 1.0, 1.0), 4);
+//!       .with_border(Color::from_rgba(0.0, 0.0, 1.0, 1.0), 4.0);
 //!
 //!     canvas.draw_shape(&circle);
 //!   }
 //! }
 //! ```
 
-use super::Drawable;
+use std::f32::consts::FRAC_1_SQRT_2;
+
+use super::{clamp_coordinate, Bounds, Drawable};
 use crate::Color;
 
 #[derive(Clone)]
 pub struct Circle {
-  x: i16,
-  y: i16,
-  #[allow(dead_code)]
-  radius: i16,
-  radius_squared: i32,
+  x: f32,
+  y: f32,
+  radius: f32,
   fill: Option<Color>,
   border: Option<Color>,
-  border_width: i16,
+  border_width: f32,
 }
 
 impl Circle {
@@ -38,24 +38,41 @@ impl Circle {
   /// Creates a new circle with the given `x` and `y` as its center,
   /// with a radius of `radius`, and a fill and border color of `None`
   ///
+  /// The center and radius can be between pixels, which is drawn smoothly with
+  /// [`RenderQuality::Antialiased`](crate::RenderQuality::Antialiased)
+  ///
   /// # Examples
   /// ```
   /// use waow::*;
   ///
-  /// let circle = shapes::Circle::new(50, 50, 30);
+  /// let circle = shapes::Circle::new(50.0, 50.5, 30.25);
   /// ````
-  pub fn new(x: i16, y: i16, radius: i16) -> Self {
+  pub fn new(x: f32, y: f32, radius: f32) -> Self {
     return Self {
       x,
       y,
       radius,
-      radius_squared: (radius * radius) as i32,
       fill: None,
       border: None,
-      border_width: 0,
+      border_width: 0.0,
     };
   }
 
+  /// creates a new circle from whole pixel coordinates
+  ///
+  /// Creates a new circle centered on the middle of the pixel at
+  /// (`x`, `y`), with a radius of `radius`
+  ///
+  /// # Examples
+  /// ```
+  /// use waow::*;
+  ///
+  /// let circle = shapes::Circle::new_int(50, 50, 30);
+  /// ````
+  pub fn new_int(x: i32, y: i32, radius: i32) -> Self {
+    return Self::new(x as f32 + 0.5, y as f32 + 0.5, radius as f32);
+  }
+
   /// A builder that adds a fill to a circle
   ///
   /// Takes an instance of a circle and returns an instance with
@@ -65,7 +82,7 @@ impl Circle {
   /// ```
   /// use waow::*;
   ///
-  /// let circle = shapes::Circle::new(10, 10, 30)
+  /// let circle = shapes::Circle::new(10.0, 10.0, 30.0)
   ///   .with_fill(Color::from_rgba(1.0, 0.0, 0.0, 1.0));
   /// ```
   pub fn with_fill(mut self, color: Color) -> Self {
@@ -82,10 +99,10 @@ impl Circle {
   /// ```
   /// use waow::*;
   ///
-  /// let circle = shapes::Circle::new(10, 10, 30)
-  ///   .with_border(Color::from_rgba(0.0, 0.0, 1.0, 1.0), 4);
+  /// let circle = shapes::Circle::new(10.0, 10.0, 30.0)
+  ///   .with_border(Color::from_rgba(0.0, 0.0, 1.0, 1.0), 4.0);
   /// ```
-  pub fn with_border(mut self, color: Color, width: i16) -> Self {
+  pub fn with_border(mut self, color: Color, width: f32) -> Self {
     self.border = Some(color);
     self.border_width = width;
     return self;
@@ -93,25 +110,77 @@ impl Circle {
 }
 
 impl Drawable for Circle {
-  fn get_color(&self, x: i16, y: i16) -> Option<Color> {
-    let dx = (x - self.x) as i32;
-    let dy = (y - self.y) as i32;
+  fn get_color(&self, x: i32, y: i32) -> Option<Color> {
+    return self.sample(x as f32 + 0.5, y as f32 + 0.5);
+  }
+
+  fn get_color_antialiased(&self, x: i32, y: i32) -> Option<Color> {
+    let dx = x as f32 + 0.5 - self.x;
+    let dy = y as f32 + 0.5 - self.y;
+    let dist = (dx * dx + dy * dy).sqrt();
+
+    let outer = coverage(self.radius, dist);
+    if outer <= 0.0 {
+      return None;
+    }
+
+    let inner = if self.border.is_some() {
+      coverage(self.radius - self.border_width, dist)
+    } else {
+      outer
+    };
+
+    return Color::from_coverage([(self.fill, inner), (self.border, outer - inner)]);
+  }
+
+  fn sample(&self, x: f32, y: f32) -> Option<Color> {
+    let dx = x - self.x;
+    let dy = y - self.y;
     let dist_sq = dx * dx + dy * dy;
 
-    if dist_sq > self.radius_squared {
+    if dist_sq > self.radius * self.radius {
       return None;
     }
 
-    if let Some(_) = self.border {
-      if dist_sq > ((self.radius - self.border_width) * (self.radius - self.border_width)) as i32 {
-        return self.border;
-      }
+    let inner_radius = self.radius - self.border_width;
+    if self.border.is_some() && dist_sq > inner_radius * inner_radius {
+      return self.border;
     }
 
-    if let Some(_) = self.fill {
-      return self.fill;
+    return self.fill;
+  }
+
+  fn get_bounds(&self) -> Option<Bounds> {
+    // includes the edge pixels that are partly covered when antialiased
+    return Some(Bounds::from_edges(
+      self.x - self.radius - 1.0,
+      self.y - self.radius - 1.0,
+      self.x + self.radius + 1.0,
+      self.y + self.radius + 1.0,
+    ));
+  }
+
+  fn get_row_span(&self, y: i32) -> Option<(i32, i32)> {
+    // includes the pixels around the edge that are drawn when antialiased,
+    // and those with a corner inside the circle, which can be supersampled
+    let dy = y as f32 + 0.5 - self.y;
+    let radius = self.radius + FRAC_1_SQRT_2;
+    let remaining = radius * radius - dy * dy;
+    if remaining < 0.0 {
+      return None;
     }
 
-    return None;
+    // the pixels whose centers are within half_width of the center
+    let half_width = remaining.sqrt();
+    return Some((
+      clamp_coordinate(self.x - half_width - 0.5).ceil() as i32,
+      clamp_coordinate(self.x + half_width - 0.5).floor() as i32 + 1,
+    ));
   }
 }
+
+/// The fraction of a pixel `dist` away from the center
+/// of a circle of `radius` that is inside the circle
+fn coverage(radius: f32, dist: f32) -> f64 {
+  return (radius + 0.5 - dist).clamp(0.0, 1.0) as f64;
+}
@@ -11,7 +11,7 @@
 //! impl App {
 //!   pub fn create() -> Self {
 //!     return Self {
-//!       image: shapes::Image::new(0, 0, 256, 256)
+//!       image: shapes::Image::new(0.0, 0.0, 256, 256)
 //!     };
 //!   }
 //! }
@@ -30,15 +30,15 @@
 //! }
 //! ```
 
-use super::Drawable;
+use super::{Bounds, Drawable};
 use crate::Color;
 
 #[derive(Clone)]
 pub struct Image {
-  x: i16,
-  y: i16,
-  width: i16,
-  height: i16,
+  x: f32,
+  y: f32,
+  width: u32,
+  height: u32,
   pixels: Vec<Vec<Color>>,
 }
 
@@ -46,15 +46,15 @@ impl Image {
   /// creates a new image
   ///
   /// Creates a new image withe the given `x` and `y` as its top right corner,
-  /// with a size of `width` x `height` and fills it with black
+  /// with a size of `width` x `height` pixels and fills it with black
   ///
   /// # Examples
   /// ```
   /// use waow::*;
   ///
-  /// let img = shapes::Image::new(30, 30, 100, 100);
+  /// let img = shapes::Image::new(30.0, 30.0, 100, 100);
   /// ```
-  pub fn new(x: i16, y: i16, width: i16, height: i16) -> Self {
+  pub fn new(x: f32, y: f32, width: u32, height: u32) -> Self {
     let mut pixels = Vec::<Vec<Color>>::new();
 
     for _ in 0..width {
@@ -75,44 +75,89 @@ impl Image {
     };
   }
 
+  /// creates a new image from whole pixel coordinates
+  ///
+  /// Works the same as [`Image::new()`]
+  ///
+  /// # Examples
+  /// ```
+  /// use waow::*;
+  ///
+  /// let img = shapes::Image::new_int(30, 30, 100, 100);
+  /// ```
+  pub fn new_int(x: i32, y: i32, width: u32, height: u32) -> Self {
+    return Self::new(x as f32, y as f32, width, height);
+  }
+
   /// Sets the color of a pixel in an image
   ///
   /// Sets the `color` of (`x`, `y`) in relative coordinates
   ///
+  /// # Panics
+  /// panics if the position is outside of the image
+  ///
   /// # Examples
   /// ```
   /// use waow::*;
   ///
-  /// let mut img = shapes::Image::new(0, 0, 255, 255);
+  /// let mut img = shapes::Image::new(0.0, 0.0, 255, 255);
   /// for x in 0..255 {
   ///   for y in 0..255 {
   ///     img.set_pixel(x, y, Color::from_rgba_int(x as u8, y as u8, 128, 255));
   ///   }
   /// }
   /// ````
-  pub fn set_pixel(&mut self, x: i16, y: i16, color: Color) {
-    //TODO: fix bounds check
-    if x < self.x || x >= self.x + self.width || y < self.y || y >= self.y + self.height {
+  pub fn set_pixel(&mut self, x: u32, y: u32, color: Color) {
+    if x >= self.width || y >= self.height {
       panic!("Pixel position outside of image bounds ({}, {})", x, y);
     }
     self.pixels[x as usize][y as usize] = color;
   }
 
-  //TODO: add get_pixel fn
+  /// Gets the color of a pixel in an image
+  ///
+  /// Gets the color of (`x`, `y`) in relative coordinates
+  ///
+  /// # Panics
+  /// panics if the position is outside of the image
+  ///
+  /// # Examples
+  /// ```
+  /// use waow::*;
+  ///
+  /// let mut img = shapes::Image::new(30.0, 30.0, 10, 10);
+  /// img.set_pixel(2, 3, Color::from_rgba(1.0, 0.0, 0.0, 1.0));
+  ///
+  /// assert_eq!(img.get_pixel(2, 3).as_bytes(), [255, 0, 0, 255]);
+  /// ```
+  pub fn get_pixel(&self, x: u32, y: u32) -> Color {
+    if x >= self.width || y >= self.height {
+      panic!("Pixel position outside of image bounds ({}, {})", x, y);
+    }
+    return self.pixels[x as usize][y as usize];
+  }
 }
 
 impl Drawable for Image {
-  fn get_color(&self, x: i16, y: i16) -> Option<Color> {
-    if x < self.x || x >= self.x + self.width || y < self.y || y >= self.y + self.height {
+  fn get_color(&self, x: i32, y: i32) -> Option<Color> {
+    return self.sample(x as f32 + 0.5, y as f32 + 0.5);
+  }
+
+  fn sample(&self, x: f32, y: f32) -> Option<Color> {
+    let (x, y) = ((x - self.x).floor(), (y - self.y).floor());
+    if x < 0.0 || x >= self.width as f32 || y < 0.0 || y >= self.height as f32 {
       return None;
     }
-    Some(
-      *self
-        .pixels
-        .get(x as usize)
-        .unwrap()
-        .get(y as usize)
-        .unwrap(),
-    )
+
+    return Some(self.pixels[x as usize][y as usize]);
+  }
+
+  fn get_bounds(&self) -> Option<Bounds> {
+    return Some(Bounds::from_edges(
+      self.x,
+      self.y,
+      self.x + self.width as f32,
+      self.y + self.height as f32,
+    ));
   }
 }
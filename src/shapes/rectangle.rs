@@ -9,27 +9,27 @@
 //! impl Run for App {
 //!   fn start(&mut self, _canvas: &mut Canvas) {}
 //!   fn draw(&mut self, canvas: &mut Canvas, _input: &Input) {
-//!     let rectangle = shapes::Rectangle::new(10, 10, 30, 50)
+//!     let rectangle = shapes::Rectangle::new(10.0, 10.0, 30.0, 50.0)
 //!       .with_fill(Color::from_rgba(1.0, 0.0, 0.0, 1.0))
-//!       .with_border(Color::from_rgba(0.0, 0.0, 1.0, 1.0), 4);
+//!       .with_border(Color::from_rgba(0.0, 0.0, 1.0, 1.0), 4.0);
 //!
 //!     canvas.draw_shape(&rectangle);
 //!   }
 //! }
 //! ```
 
-use super::Drawable;
+use super::{Bounds, Drawable};
 use crate::Color;
 
 #[derive(Clone)]
 pub struct Rectangle {
-  x: i16,
-  y: i16,
-  width: i16,
-  height: i16,
+  x: f32,
+  y: f32,
+  width: f32,
+  height: f32,
   fill: Option<Color>,
   border: Option<Color>,
-  border_width: i16,
+  border_width: f32,
 }
 
 impl Rectangle {
@@ -38,13 +38,16 @@ impl Rectangle {
   /// Creates a new rectangle withe the given `x` and `y` as its top right corner,
   /// with a size of `width` x `height`, and no fill or border
   ///
+  /// The position and size can be between pixels, which is drawn smoothly with
+  /// [`RenderQuality::Antialiased`](crate::RenderQuality::Antialiased)
+  ///
   /// # Examples
   /// ```
   /// use waow::*;
   ///
-  /// let rectangle = shapes::Rectangle::new(10, 10, 30, 50);
+  /// let rectangle = shapes::Rectangle::new(10.0, 10.5, 30.0, 50.25);
   /// ````
-  pub fn new(x: i16, y: i16, width: i16, height: i16) -> Self {
+  pub fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
     return Self {
       x,
       y,
@@ -52,10 +55,24 @@ impl Rectangle {
       height,
       fill: None,
       border: None,
-      border_width: 0,
+      border_width: 0.0,
     };
   }
 
+  /// creates a new rectangle from whole pixel coordinates
+  ///
+  /// Works the same as [`Rectangle::new()`]
+  ///
+  /// # Examples
+  /// ```
+  /// use waow::*;
+  ///
+  /// let rectangle = shapes::Rectangle::new_int(10, 10, 30, 50);
+  /// ````
+  pub fn new_int(x: i32, y: i32, width: i32, height: i32) -> Self {
+    return Self::new(x as f32, y as f32, width as f32, height as f32);
+  }
+
   /// A builder that adds a fill to a rectangle
   ///
   /// Takes an instance of a rectangle and returns an instance with
@@ -65,7 +82,7 @@ impl Rectangle {
   /// ```
   /// use waow::*;
   ///
-  /// let rectangle = shapes::Rectangle::new(10, 10, 30, 50)
+  /// let rectangle = shapes::Rectangle::new(10.0, 10.0, 30.0, 50.0)
   ///   .with_fill(Color::from_rgba(1.0, 0.0, 0.0, 1.0));
   /// ```
   pub fn with_fill(mut self, color: Color) -> Self {
@@ -82,10 +99,10 @@ impl Rectangle {
   /// ```
   /// use waow::*;
   ///
-  /// let rectangle = shapes::Rectangle::new(10, 10, 30, 50)
-  ///   .with_border(Color::from_rgba(0.0, 0.0, 1.0, 1.0), 4);
+  /// let rectangle = shapes::Rectangle::new(10.0, 10.0, 30.0, 50.0)
+  ///   .with_border(Color::from_rgba(0.0, 0.0, 1.0, 1.0), 4.0);
   /// ```
-  pub fn with_border(mut self, color: Color, width: i16) -> Self {
+  pub fn with_border(mut self, color: Color, width: f32) -> Self {
     self.border = Some(color);
     self.border_width = width;
     return self;
@@ -93,25 +110,57 @@ impl Rectangle {
 }
 
 impl Drawable for Rectangle {
-  fn get_color(&self, x: i16, y: i16) -> Option<Color> {
-    if x < self.x || y < self.y || x >= self.x + self.width || y >= self.y + self.height {
+  fn get_color(&self, x: i32, y: i32) -> Option<Color> {
+    return self.sample(x as f32 + 0.5, y as f32 + 0.5);
+  }
+
+  fn get_color_antialiased(&self, x: i32, y: i32) -> Option<Color> {
+    let (right, bottom) = (self.x + self.width, self.y + self.height);
+
+    let outer = overlap(x, self.x, right) * overlap(y, self.y, bottom);
+    if outer <= 0.0 {
       return None;
     }
 
-    if let Some(_) = self.border {
-      if x < self.x + self.border_width
-        || y < self.y + self.border_width
-        || x >= self.x + self.width - self.border_width
-        || y >= self.y + self.height - self.border_width
-      {
-        return self.border;
-      }
+    let inner = if self.border.is_some() {
+      let border = self.border_width;
+      overlap(x, self.x + border, right - border) * overlap(y, self.y + border, bottom - border)
+    } else {
+      outer
+    };
+
+    return Color::from_coverage([(self.fill, inner), (self.border, outer - inner)]);
+  }
+
+  fn sample(&self, x: f32, y: f32) -> Option<Color> {
+    let (right, bottom) = (self.x + self.width, self.y + self.height);
+
+    if x < self.x || y < self.y || x >= right || y >= bottom {
+      return None;
     }
 
-    if let Some(_) = self.fill {
-      return self.fill;
+    let border = self.border_width;
+    if self.border.is_some()
+      && (x < self.x + border || y < self.y + border || x >= right - border || y >= bottom - border)
+    {
+      return self.border;
     }
 
-    return None;
+    return self.fill;
+  }
+
+  fn get_bounds(&self) -> Option<Bounds> {
+    return Some(Bounds::from_edges(
+      self.x,
+      self.y,
+      self.x + self.width,
+      self.y + self.height,
+    ));
   }
 }
+
+/// The fraction of the pixel at `pixel` that is between `start` and `end`
+fn overlap(pixel: i32, start: f32, end: f32) -> f64 {
+  let pixel = pixel as f32;
+  return (end.min(pixel + 1.0) - start.max(pixel)).clamp(0.0, 1.0) as f64;
+}
@@ -0,0 +1,102 @@
+//! Keeps track of time while an app runs
+//!
+//! [`Run::update()`](crate::Run::update) is called at a fixed rate,
+//! no matter how often frames are drawn, and the time between frames
+//! is shown on the canvas, see [`Canvas::get_delta_time()`](crate::Canvas::get_delta_time)
+
+use std::time::Duration;
+
+/// The most time a single frame can add to the fixed timestep, so one
+/// slow frame doesn't lead to more and more updates to catch up
+pub(crate) const MAX_FRAME_TIME: Duration = Duration::from_millis(250);
+
+/// How far the smoothed frame rate moves towards the frame rate of each
+/// new frame, from `0.0` (never changes) to `1.0` (no smoothing)
+const FPS_SMOOTHING: f32 = 0.1;
+
+/// Counts how many fixed length updates fit into the time that has passed
+pub(crate) struct FixedTimestep {
+  step: Duration,
+  accumulator: Duration,
+}
+
+impl FixedTimestep {
+  /// Creates a timestep that is due `rate` times per second
+  ///
+  /// # Panics
+  /// panics if `rate` is `0`
+  pub(crate) fn new(rate: u32) -> Self {
+    assert!(rate > 0, "update rate must be greater than 0");
+    return Self {
+      step: Duration::from_secs(1) / rate,
+      accumulator: Duration::ZERO,
+    };
+  }
+
+  /// Adds `elapsed` to the time waiting to be simulated, returning
+  /// the number of updates that are now due
+  pub(crate) fn advance(&mut self, elapsed: Duration) -> u32 {
+    self.accumulator += elapsed;
+
+    let mut updates = 0;
+    while self.accumulator >= self.step {
+      self.accumulator -= self.step;
+      updates += 1;
+    }
+    return updates;
+  }
+
+  /// Gets the length of each update in seconds
+  pub(crate) fn get_delta_time(&self) -> f32 {
+    return self.step.as_secs_f32();
+  }
+
+  /// Gets how far the time is between the last update and the next,
+  /// from `0.0` to `1.0`
+  pub(crate) fn get_interpolation(&self) -> f32 {
+    return self.accumulator.as_secs_f32() / self.step.as_secs_f32();
+  }
+}
+
+/// Counts the frames drawn and the time between them
+#[derive(Default)]
+pub(crate) struct FrameClock {
+  frame_count: u64,
+  elapsed: Duration,
+  delta: Duration,
+  fps: f32,
+}
+
+impl FrameClock {
+  /// Starts a new frame, `delta` after the last one
+  pub(crate) fn tick(&mut self, delta: Duration) {
+    self.frame_count += 1;
+    self.elapsed += delta;
+    self.delta = delta;
+
+    if delta > Duration::ZERO {
+      let fps = 1.0 / delta.as_secs_f32();
+      self.fps = if self.fps == 0.0 {
+        fps
+      } else {
+        self.fps + (fps - self.fps) * FPS_SMOOTHING
+      };
+    }
+  }
+
+  pub(crate) fn get_frame_count(&self) -> u64 {
+    return self.frame_count;
+  }
+
+  pub(crate) fn get_elapsed(&self) -> Duration {
+    return self.elapsed;
+  }
+
+  pub(crate) fn get_delta(&self) -> Duration {
+    return self.delta;
+  }
+
+  pub(crate) fn get_fps(&self) -> f32 {
+    return self.fps;
+  }
+}
@@ -0,0 +1,211 @@
+//! Represents a 2D affine transformation, made of any combination
+//! of translations, rotations and scales
+//!
+//! The canvas keeps a current transform that is applied to every
+//! shape drawn, see [`Canvas::translate()`](crate::Canvas::translate)
+
+use crate::{
+  canvas::supersample,
+  shapes::{Bounds, Drawable},
+  Color,
+};
+
+/// The number of samples taken along each side of a pixel when drawing
+/// a transformed shape with antialiasing
+const ANTIALIAS_SAMPLES: u8 = 4;
+
+/// Maps a point (`x`, `y`) to (`a * x + c * y + e`, `b * x + d * y + f`)
+///
+/// # Examples
+/// ```
+/// use waow::*;
+///
+/// let transform = Transform::translation(10.0, 0.0).multiply(&Transform::scaling(2.0, 3.0));
+///
+/// assert_eq!(transform.apply(1.0, 1.0), (12.0, 3.0));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform {
+  a: f32,
+  b: f32,
+  c: f32,
+  d: f32,
+  e: f32,
+  f: f32,
+}
+
+impl Transform {
+  /// A transform that leaves every point where it is
+  pub fn identity() -> Self {
+    return Self {
+      a: 1.0,
+      b: 0.0,
+      c: 0.0,
+      d: 1.0,
+      e: 0.0,
+      f: 0.0,
+    };
+  }
+
+  /// A transform that moves every point by `x` and `y`
+  pub fn translation(x: f32, y: f32) -> Self {
+    return Self {
+      e: x,
+      f: y,
+      ..Self::identity()
+    };
+  }
+
+  /// A transform that rotates every point clockwise around
+  /// the origin by `angle` in radians
+  pub fn rotation(angle: f32) -> Self {
+    let (sin, cos) = angle.sin_cos();
+    return Self {
+      a: cos,
+      b: sin,
+      c: -sin,
+      d: cos,
+      ..Self::identity()
+    };
+  }
+
+  /// A transform that scales every point away from the origin
+  /// by `x` horizontally and `y` vertically
+  pub fn scaling(x: f32, y: f32) -> Self {
+    return Self {
+      a: x,
+      d: y,
+      ..Self::identity()
+    };
+  }
+
+  /// Combines two transforms into one that applies `other` first,
+  /// and then this transform
+  pub fn multiply(&self, other: &Transform) -> Transform {
+    return Self {
+      a: self.a * other.a + self.c * other.b,
+      b: self.b * other.a + self.d * other.b,
+      c: self.a * other.c + self.c * other.d,
+      d: self.b * other.c + self.d * other.d,
+      e: self.a * other.e + self.c * other.f + self.e,
+      f: self.b * other.e + self.d * other.f + self.f,
+    };
+  }
+
+  /// Gets the transform that undoes this transform
+  ///
+  /// returns `None` if the transform can't be undone,
+  /// such as when it scales by `0.0`
+  ///
+  /// # Examples
+  /// ```
+  /// use waow::*;
+  ///
+  /// let transform = Transform::translation(5.0, 5.0).multiply(&Transform::scaling(2.0, 2.0));
+  /// let inverse = transform.inverse().unwrap();
+  ///
+  /// assert_eq!(inverse.apply(15.0, 25.0), (5.0, 10.0));
+  /// assert_eq!(Transform::scaling(0.0, 1.0).inverse(), None);
+  /// ```
+  pub fn inverse(&self) -> Option<Transform> {
+    let determinant = self.a * self.d - self.b * self.c;
+    if determinant == 0.0 || !determinant.is_finite() {
+      return None;
+    }
+
+    return Some(Self {
+      a: self.d / determinant,
+      b: -self.b / determinant,
+      c: -self.c / determinant,
+      d: self.a / determinant,
+      e: (self.c * self.f - self.d * self.e) / determinant,
+      f: (self.b * self.e - self.a * self.f) / determinant,
+    });
+  }
+
+  /// Applies the transform to the point (`x`, `y`)
+  pub fn apply(&self, x: f32, y: f32) -> (f32, f32) {
+    return (
+      self.a * x + self.c * y + self.e,
+      self.b * x + self.d * y + self.f,
+    );
+  }
+
+  /// Checks whether the transform leaves every point where it is
+  pub fn is_identity(&self) -> bool {
+    return *self == Self::identity();
+  }
+
+  /// Gets the smallest bounds that contain `bounds` once transformed
+  pub(crate) fn apply_bounds(&self, bounds: &Bounds) -> Bounds {
+    let (left, top) = (bounds.x as f32, bounds.y as f32);
+    let (right, bottom) = (bounds.right() as f32, bounds.bottom() as f32);
+    let corners = [
+      self.apply(left, top),
+      self.apply(right, top),
+      self.apply(left, bottom),
+      self.apply(right, bottom),
+    ];
+
+    let min_x = corners.iter().map(|corner| corner.0).fold(f32::MAX, f32::min);
+    let min_y = corners.iter().map(|corner| corner.1).fold(f32::MAX, f32::min);
+    let max_x = corners.iter().map(|corner| corner.0).fold(f32::MIN, f32::max);
+    let max_y = corners.iter().map(|corner| corner.1).fold(f32::MIN, f32::max);
+
+    return Bounds::from_edges(min_x, min_y, max_x, max_y);
+  }
+}
+
+impl Default for Transform {
+  fn default() -> Self {
+    return Self::identity();
+  }
+}
+
+/// A shape drawn with a transform, which samples the shape at
+/// the point each pixel maps back to
+pub(crate) struct TransformedShape {
+  shape: Box<dyn Drawable>,
+  transform: Transform,
+  inverse: Transform,
+}
+
+impl TransformedShape {
+  /// returns `None` if the transform can't be undone, since
+  /// the shape would not cover any pixels
+  pub(crate) fn new(shape: Box<dyn Drawable>, transform: Transform) -> Option<Self> {
+    return transform.inverse().map(|inverse| Self {
+      shape,
+      transform,
+      inverse,
+    });
+  }
+}
+
+impl Drawable for TransformedShape {
+  fn get_color(&self, x: i32, y: i32) -> Option<Color> {
+    return self.sample(x as f32 + 0.5, y as f32 + 0.5);
+  }
+
+  fn get_color_antialiased(&self, x: i32, y: i32) -> Option<Color> {
+    return supersample(self, x, y, ANTIALIAS_SAMPLES);
+  }
+
+  fn sample(&self, x: f32, y: f32) -> Option<Color> {
+    let (x, y) = self.inverse.apply(x, y);
+    return self.shape.sample(x, y);
+  }
+
+  fn get_bounds(&self) -> Option<Bounds> {
+    return self.shape.get_bounds().map(|bounds| {
+      let bounds = self.transform.apply_bounds(&bounds);
+      // leaves room for the edges that are blended when antialiased
+      Bounds::new(
+        bounds.x - 1,
+        bounds.y - 1,
+        bounds.width + 2,
+        bounds.height + 2,
+      )
+    });
+  }
+}